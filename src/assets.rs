@@ -4,8 +4,7 @@ use gba::{
     Align4,
     fixed::i32fx8,
     include_aligned_bytes,
-    mmio::{BG_PALETTE, CHARBLOCK0_8BPP, OBJ_ATTR_ALL, OBJ_PALETTE, OBJ_TILES, TEXT_SCREENBLOCKS},
-    prelude::{ObjAttr, ObjAttrWriteExt, ObjDisplayStyle},
+    mmio::{BG_PALETTE, CHARBLOCK0_8BPP, OBJ_PALETTE, OBJ_TILES, TEXT_SCREENBLOCKS},
     video::Color,
 };
 
@@ -23,13 +22,55 @@ pub static BACKGROUND_TILES: Align4<[u8; 10240]> =
 pub const BACKGROUND_TILE_COLS_PER_ROW: usize = 16;
 pub static COIN_TILE: Align4<[u8; 256]> = include_aligned_bytes!("../asset_out/coin.sprite");
 pub static MARIO_TILE: Align4<[u8; 2048]> = include_aligned_bytes!("../asset_out/mario.sprite");
-pub static POINT_TILE: Align4<[u8; 512]> = include_aligned_bytes!("../asset_out/score.sprite");
+pub static POINT_TILE: Align4<[u8; 576]> = include_aligned_bytes!("../asset_out/score.sprite");
+pub static GOOMBA_TILE: Align4<[u8; 512]> = include_aligned_bytes!("../asset_out/goomba.sprite");
+pub static KOOPA_TILE: Align4<[u8; 1536]> = include_aligned_bytes!("../asset_out/koopa.sprite");
+pub static PIRANHA_TILE: Align4<[u8; 1024]> = include_aligned_bytes!("../asset_out/piranha.sprite");
+pub static ITEM_TILE: Align4<[u8; 768]> = include_aligned_bytes!("../asset_out/items.sprite");
+pub static LIFT_TILE: Align4<[u8; 128]> = include_aligned_bytes!("../asset_out/lift.sprite");
+pub static SPRINGBOARD_TILE: Align4<[u8; 768]> =
+    include_aligned_bytes!("../asset_out/springboard.sprite");
+pub static FIREBAR_TILE: Align4<[u8; 64]> = include_aligned_bytes!("../asset_out/firebar.sprite");
+pub static BOWSER_TILE: Align4<[u8; 2048]> = include_aligned_bytes!("../asset_out/bowser.sprite");
+pub static BOWSER_FIRE_TILE: Align4<[u8; 256]> =
+    include_aligned_bytes!("../asset_out/bowser_fire.sprite");
+pub static HAMMER_BRO_TILE: Align4<[u8; 1024]> =
+    include_aligned_bytes!("../asset_out/hammer_bro.sprite");
+pub static LAKITU_TILE: Align4<[u8; 1024]> = include_aligned_bytes!("../asset_out/lakitu.sprite");
+pub static SPINY_TILE: Align4<[u8; 512]> = include_aligned_bytes!("../asset_out/spiny.sprite");
+pub static HAMMER_TILE: Align4<[u8; 64]> = include_aligned_bytes!("../asset_out/hammer.sprite");
+pub static BULLET_BILL_TILE: Align4<[u8; 256]> =
+    include_aligned_bytes!("../asset_out/bullet_bill.sprite");
+pub static CHEEP_CHEEP_TILE: Align4<[u8; 512]> =
+    include_aligned_bytes!("../asset_out/cheep_cheep.sprite");
+pub static BRICK_DEBRIS_TILE: Align4<[u8; 64]> =
+    include_aligned_bytes!("../asset_out/brick_debris.sprite");
 
 pub const COIN_TILE_IDX_START: usize = 1;
 pub const MARIO_TILE_IDX_START: usize = COIN_TILE_IDX_START + COIN_TILE.0.len() / 64;
 pub const POINT_TILE_IDX_START: usize = MARIO_TILE_IDX_START + MARIO_TILE.0.len() / 64;
 pub const BRICK_IDX_START: usize = POINT_TILE_IDX_START + POINT_TILE.0.len() / 64;
 pub const USED_BLOCK_IDX_START: usize = BRICK_IDX_START + 4;
+pub const GOOMBA_TILE_IDX_START: usize = USED_BLOCK_IDX_START + 4;
+pub const KOOPA_TILE_IDX_START: usize = GOOMBA_TILE_IDX_START + GOOMBA_TILE.0.len() / 64;
+pub const PIRANHA_TILE_IDX_START: usize = KOOPA_TILE_IDX_START + KOOPA_TILE.0.len() / 64;
+pub const ITEM_TILE_IDX_START: usize = PIRANHA_TILE_IDX_START + PIRANHA_TILE.0.len() / 64;
+pub const LIFT_TILE_IDX_START: usize = ITEM_TILE_IDX_START + ITEM_TILE.0.len() / 64;
+pub const SPRINGBOARD_TILE_IDX_START: usize = LIFT_TILE_IDX_START + LIFT_TILE.0.len() / 64;
+pub const FIREBAR_TILE_IDX_START: usize =
+    SPRINGBOARD_TILE_IDX_START + SPRINGBOARD_TILE.0.len() / 64;
+pub const BOWSER_TILE_IDX_START: usize = FIREBAR_TILE_IDX_START + FIREBAR_TILE.0.len() / 64;
+pub const BOWSER_FIRE_TILE_IDX_START: usize = BOWSER_TILE_IDX_START + BOWSER_TILE.0.len() / 64;
+pub const HAMMER_BRO_TILE_IDX_START: usize =
+    BOWSER_FIRE_TILE_IDX_START + BOWSER_FIRE_TILE.0.len() / 64;
+pub const LAKITU_TILE_IDX_START: usize = HAMMER_BRO_TILE_IDX_START + HAMMER_BRO_TILE.0.len() / 64;
+pub const SPINY_TILE_IDX_START: usize = LAKITU_TILE_IDX_START + LAKITU_TILE.0.len() / 64;
+pub const HAMMER_TILE_IDX_START: usize = SPINY_TILE_IDX_START + SPINY_TILE.0.len() / 64;
+pub const BULLET_BILL_TILE_IDX_START: usize = HAMMER_TILE_IDX_START + HAMMER_TILE.0.len() / 64;
+pub const CHEEP_CHEEP_TILE_IDX_START: usize =
+    BULLET_BILL_TILE_IDX_START + BULLET_BILL_TILE.0.len() / 64;
+pub const BRICK_DEBRIS_TILE_IDX_START: usize =
+    CHEEP_CHEEP_TILE_IDX_START + CHEEP_CHEEP_TILE.0.len() / 64;
 // Affine 2 is about the same size per stride as text, if we change affine background size (use something other than AFFINE2 we will need to change this)
 pub const AFFINE2_SCREENBLOCK_START: usize = 16; // 0x0600_8000
 pub const TEXT_SCREENBLOCK_START: usize = 24; // 0x0600_C000
@@ -106,9 +147,6 @@ impl AssetManager {
 
     fn reset_internal(&mut self) {
         self.change_magic = 0;
-        let mut ottr = ObjAttr::new();
-        ottr.0 = ottr.0.with_style(ObjDisplayStyle::NotDisplayed);
-        OBJ_ATTR_ALL.iter().for_each(|va| va.write(ottr));
 
         // zero_screenblock(0);
         // zero_screenblock(1);
@@ -178,6 +216,86 @@ impl AssetManager {
                 OBJ_TILES.index(POINT_TILE_IDX_START * 2).as_usize() as *mut u8,
                 POINT_TILE.0.len(),
             );
+            copy_nonoverlapping(
+                GOOMBA_TILE.0.as_ptr(),
+                OBJ_TILES.index(GOOMBA_TILE_IDX_START * 2).as_usize() as *mut u8,
+                GOOMBA_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                KOOPA_TILE.0.as_ptr(),
+                OBJ_TILES.index(KOOPA_TILE_IDX_START * 2).as_usize() as *mut u8,
+                KOOPA_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                PIRANHA_TILE.0.as_ptr(),
+                OBJ_TILES.index(PIRANHA_TILE_IDX_START * 2).as_usize() as *mut u8,
+                PIRANHA_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                ITEM_TILE.0.as_ptr(),
+                OBJ_TILES.index(ITEM_TILE_IDX_START * 2).as_usize() as *mut u8,
+                ITEM_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                LIFT_TILE.0.as_ptr(),
+                OBJ_TILES.index(LIFT_TILE_IDX_START * 2).as_usize() as *mut u8,
+                LIFT_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                SPRINGBOARD_TILE.0.as_ptr(),
+                OBJ_TILES.index(SPRINGBOARD_TILE_IDX_START * 2).as_usize() as *mut u8,
+                SPRINGBOARD_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                FIREBAR_TILE.0.as_ptr(),
+                OBJ_TILES.index(FIREBAR_TILE_IDX_START * 2).as_usize() as *mut u8,
+                FIREBAR_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                BOWSER_TILE.0.as_ptr(),
+                OBJ_TILES.index(BOWSER_TILE_IDX_START * 2).as_usize() as *mut u8,
+                BOWSER_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                BOWSER_FIRE_TILE.0.as_ptr(),
+                OBJ_TILES.index(BOWSER_FIRE_TILE_IDX_START * 2).as_usize() as *mut u8,
+                BOWSER_FIRE_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                HAMMER_BRO_TILE.0.as_ptr(),
+                OBJ_TILES.index(HAMMER_BRO_TILE_IDX_START * 2).as_usize() as *mut u8,
+                HAMMER_BRO_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                LAKITU_TILE.0.as_ptr(),
+                OBJ_TILES.index(LAKITU_TILE_IDX_START * 2).as_usize() as *mut u8,
+                LAKITU_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                SPINY_TILE.0.as_ptr(),
+                OBJ_TILES.index(SPINY_TILE_IDX_START * 2).as_usize() as *mut u8,
+                SPINY_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                HAMMER_TILE.0.as_ptr(),
+                OBJ_TILES.index(HAMMER_TILE_IDX_START * 2).as_usize() as *mut u8,
+                HAMMER_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                BULLET_BILL_TILE.0.as_ptr(),
+                OBJ_TILES.index(BULLET_BILL_TILE_IDX_START * 2).as_usize() as *mut u8,
+                BULLET_BILL_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                CHEEP_CHEEP_TILE.0.as_ptr(),
+                OBJ_TILES.index(CHEEP_CHEEP_TILE_IDX_START * 2).as_usize() as *mut u8,
+                CHEEP_CHEEP_TILE.0.len(),
+            );
+            copy_nonoverlapping(
+                BRICK_DEBRIS_TILE.0.as_ptr(),
+                OBJ_TILES.index(BRICK_DEBRIS_TILE_IDX_START * 2).as_usize() as *mut u8,
+                BRICK_DEBRIS_TILE.0.len(),
+            );
             copy_tile(BRICK, BRICK_IDX_START);
             copy_tile(QUESTION_BLOCK_USED, USED_BLOCK_IDX_START);
             // Cga8x8Thick.bitunpack_8bpp(CHARBLOCK1_8BPP.as_region(), 0);
@@ -0,0 +1,149 @@
+use crate::{
+    assets::TEXT_SCREENBLOCK_START,
+    color::PaletteColor,
+    ewram_static,
+    level_manager::{LevelManager, is_tile},
+    levels::shared::BRIDGE,
+    math::{Powers, mod_mask_u32},
+    player::PlayerManager,
+    screen::ScreenManager,
+    screen_text::{ScreenTextManager, TextPalette},
+    static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+// One bridge segment drops every few ticks, starting next to the axe
+const COLLAPSE_TICKS: u8 = 4;
+// How long the first line of the ending stays up alone
+const SECOND_LINE_TICKS: u16 = 120;
+
+const THANK_YOU_LOC: (usize, usize) = (7, 6);
+const PRINCESS_LOC: (usize, usize) = (4, 9);
+const CASTLE_LOC: (usize, usize) = (7, 11);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CastleState {
+    Idle,
+    // Row in 8px units and col in 16px units of the next bridge segment to drop
+    Collapsing { row: usize, col: usize, ticks: u8 },
+    ThankYou { ticks: u16 },
+    Done,
+}
+
+pub struct CastleManager {
+    state: CastleState,
+    palette_handle: Option<TextPalette<3>>,
+}
+
+impl CastleManager {
+    pub const fn new() -> Self {
+        CastleManager {
+            state: CastleState::Idle,
+            palette_handle: None,
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.state = CastleState::Idle;
+        self.palette_handle = None;
+    }
+
+    pub fn on_start() {
+        Castle.init();
+    }
+
+    /// Starts dropping the bridge after the axe at row (8px units) and col (16px units) is grabbed.
+    /// The bridge runs along the row below the axe and ends just left of it.
+    pub fn start_finale(axe_row: usize, axe_col: usize) {
+        let manager = Castle.assume_init();
+        if manager.state != CastleState::Idle {
+            return;
+        }
+        PlayerManager::freeze(true);
+        manager.state = CastleState::Collapsing {
+            row: axe_row + 2,
+            col: axe_col.saturating_sub(1),
+            ticks: 0,
+        };
+    }
+
+    fn write_ending(&mut self, second_part: bool) {
+        let palette = self.palette_handle.get_or_insert_with(|| {
+            ScreenTextManager::create_palette("THANKYOUMRI!BPCESL ", PaletteColor::White)
+        });
+        if !second_part {
+            palette.write_text(
+                0,
+                TEXT_SCREENBLOCK_START,
+                "THANK YOU MARIO!",
+                THANK_YOU_LOC,
+                false,
+            );
+            return;
+        }
+        palette.write_text(
+            1,
+            TEXT_SCREENBLOCK_START,
+            "BUT OUR PRINCESS IS IN",
+            PRINCESS_LOC,
+            false,
+        );
+        palette.write_text(
+            2,
+            TEXT_SCREENBLOCK_START,
+            "ANOTHER CASTLE!",
+            CASTLE_LOC,
+            false,
+        );
+    }
+
+    pub fn tick(_tick: TickContext) {
+        let manager = Castle.assume_init();
+        match manager.state {
+            CastleState::Idle | CastleState::Done => {}
+            CastleState::Collapsing { row, col, ticks } => {
+                if ticks + 1 < COLLAPSE_TICKS {
+                    manager.state = CastleState::Collapsing {
+                        row,
+                        col,
+                        ticks: ticks + 1,
+                    };
+                    return;
+                }
+
+                let screenblock_col = mod_mask_u32(col as u32, Powers::_32) as usize;
+                if is_tile(row, screenblock_col, [BRIDGE]).is_none() {
+                    // Nothing left to drop, Bowser has fallen with the last of it
+                    PlayerManager::freeze(false);
+                    manager.write_ending(false);
+                    manager.state = CastleState::ThankYou { ticks: 0 };
+                    return;
+                }
+
+                LevelManager::clear_solid(row, col);
+                ScreenManager::shake(2, COLLAPSE_TICKS as u16 * 2);
+                manager.state = CastleState::Collapsing {
+                    row,
+                    col: col.saturating_sub(1),
+                    ticks: 0,
+                };
+            }
+            CastleState::ThankYou { ticks } => {
+                if ticks < SECOND_LINE_TICKS {
+                    manager.state = CastleState::ThankYou { ticks: ticks + 1 };
+                    return;
+                }
+                manager.write_ending(true);
+                manager.state = CastleState::Done;
+            }
+        }
+    }
+}
+
+unsafe impl StaticInitSafe for CastleManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Castle: CastleManager = CastleManager::new());
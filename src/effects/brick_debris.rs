@@ -0,0 +1,105 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::BRICK_DEBRIS_TILE_IDX_START,
+    effects::{AnimationCtx, Effect, EffectImpl, tile_to_screenspace},
+    enemies::EnemyManager,
+    items::ItemManager,
+    level_manager::LevelManager,
+    oam::{OamHandle, OamManager},
+    screen::ScreenManager,
+};
+
+const PIECES: usize = 4;
+
+// In 1/256ths of a pixel, per tick
+const DEBRIS_GRAVITY: i32 = 96;
+// Offset inside the block and starting velocity of each piece, the top two fly higher
+const PIECE_LAUNCH: [(i32, i32, i32, i32); PIECES] = [
+    (0, 0, -256, -1024),
+    (8, 0, 256, -1024),
+    (0, 8, -256, -640),
+    (8, 8, 256, -640),
+];
+const DEBRIS_TICKS: u32 = 48;
+
+pub struct BrickDebris {
+    row: usize,
+    col: usize,
+    otr: ObjAttr,
+    oam: [Option<OamHandle>; PIECES],
+}
+
+impl BrickDebris {
+    /// Shatters the brick at row (8px units) and col (16px units)
+    pub fn new(row: usize, col: usize) -> Self {
+        BrickDebris {
+            row,
+            col,
+            otr: ObjAttr::default(),
+            oam: [const { None }; PIECES],
+        }
+    }
+
+    pub fn as_effect(self) -> Effect {
+        Effect::BrickDebris(self)
+    }
+}
+
+impl EffectImpl for BrickDebris {
+    fn tick(&mut self, ctx: AnimationCtx) -> bool {
+        if ctx.animation_tick >= DEBRIS_TICKS {
+            return false;
+        }
+
+        if ctx.animation_tick == 0 {
+            let mut otr = ObjAttr::new();
+            otr.set_style(ObjDisplayStyle::Normal);
+            otr.0 = otr
+                .0
+                .with_shape(ObjShape::Square)
+                .with_mode(ObjEffectMode::Normal)
+                .with_bpp8(true);
+            otr.1 = otr.1.with_size(0);
+            otr.2 = otr
+                .2
+                .with_tile_id(BRICK_DEBRIS_TILE_IDX_START as u16 * 2)
+                .with_priority(0)
+                .with_palbank(0);
+            self.otr = otr;
+            self.oam = core::array::from_fn(|_| OamManager::alloc());
+            LevelManager::clear_solid(self.row, self.col);
+            // Anything standing on the brick gets hit along with it
+            EnemyManager::bump_from_below(self.row, self.col);
+            ItemManager::bump_from_below(self.row, self.col);
+        }
+
+        return true;
+    }
+
+    fn post_tick(&mut self, ctx: AnimationCtx) {
+        let screen = ScreenManager::get_screen_info();
+        let (base_x, base_y) = tile_to_screenspace(self.row, self.col, &screen);
+        let t = ctx.animation_tick as i32;
+
+        for (oam, (dx, dy, vel_x, vel_y)) in self.oam.iter().zip(PIECE_LAUNCH) {
+            let Some(oam) = oam else {
+                continue;
+            };
+            let x = base_x + dx + ((vel_x * t) >> 8);
+            let y = base_y + dy + ((vel_y * t + DEBRIS_GRAVITY * t * t / 2) >> 8);
+            if x < -8 || x >= 240 || y < -8 || y >= 160 {
+                oam.hide();
+                continue;
+            }
+
+            let mut otr = self.otr;
+            otr.set_x(x as u16);
+            otr.set_y(y as u16);
+            // Tumble by flipping every few frames, the left pieces spin the other way
+            let flip = (ctx.animation_tick >> 2) & 1 != 0;
+            otr.1 = otr.1.with_hflip(flip).with_vflip(flip != (dx == 0));
+            oam.write(otr);
+        }
+    }
+}
@@ -2,7 +2,8 @@ use gba::prelude::*;
 
 use crate::{
     assets::COIN_TILE_IDX_START,
-    effects::{AnimationCtx, Effect, EffectImpl, tile_to_screenspace},
+    effects::{AnimationCtx, Effect, EffectImpl, EffectsManager, tile_to_screenspace},
+    oam::{AffineHandle, OamHandle, OamManager},
     screen::ScreenManager,
 };
 
@@ -10,6 +11,8 @@ pub struct CoinUp {
     row: usize,
     col: usize,
     otr: ObjAttr,
+    oam: Option<OamHandle>,
+    spin: Option<AffineHandle>,
 }
 
 impl CoinUp {
@@ -18,6 +21,8 @@ impl CoinUp {
             row,
             col,
             otr: ObjAttr::default(),
+            oam: None,
+            spin: None,
         }
     }
 
@@ -29,7 +34,6 @@ impl CoinUp {
 impl EffectImpl for CoinUp {
     fn tick(&mut self, ctx: AnimationCtx) -> bool {
         if ctx.animation_tick >= 16 {
-            OBJ_ATTR_ALL.index(2).write(ObjAttr::default());
             return false;
         }
 
@@ -37,19 +41,29 @@ impl EffectImpl for CoinUp {
             let mut otr = ObjAttr::new();
             otr.set_x(32);
             otr.set_y(32);
-            otr.set_style(ObjDisplayStyle::Affine);
+            // All coins share the spin EffectsManager animates
+            self.spin = EffectsManager::coin_spin();
+            otr.set_style(if self.spin.is_some() {
+                ObjDisplayStyle::Affine
+            } else {
+                ObjDisplayStyle::Normal
+            });
             otr.0 = otr
                 .0
                 .with_shape(ObjShape::Square)
                 .with_mode(ObjEffectMode::Normal)
                 .with_bpp8(true);
-            otr.1 = otr.1.with_size(1).with_affine_index(1);
+            otr.1 = otr
+                .1
+                .with_size(1)
+                .with_affine_index(self.spin.as_ref().map_or(0, AffineHandle::idx));
             otr.2 = otr
                 .2
                 .with_tile_id((COIN_TILE_IDX_START * 2) as u16)
                 .with_priority(0)
                 .with_palbank(0);
             self.otr = otr;
+            self.oam = OamManager::alloc();
         }
 
         return true;
@@ -66,6 +80,8 @@ impl EffectImpl for CoinUp {
         self.otr
             .set_y(base_y.saturating_sub(offset).clamp(0, 256) as u16);
 
-        OBJ_ATTR_ALL.index(2).write(self.otr);
+        if let Some(oam) = &self.oam {
+            oam.write(self.otr);
+        }
     }
 }
@@ -4,20 +4,27 @@ use enum_dispatch::enum_dispatch;
 use gba::prelude::*;
 
 use crate::{
-    effects::{coin_up::CoinUp, points::Points, tile_bounce::TileBounce},
+    effects::{
+        brick_debris::BrickDebris, coin_up::CoinUp, mosaic::Mosaic, points::Points,
+        springboard::Springboard, tile_bounce::TileBounce, timeline::Timeline,
+    },
     ewram_static,
     fixed_bag::FixedBag,
-    fixed_queue::FixedQueue,
     gba_warning,
     math::mod_mask_u32,
+    oam::{AffineHandle, OamManager, Transform},
     screen::ScreenInfo,
     static_init::StaticInitSafe,
     tick::TickContext,
 };
 
+pub mod brick_debris;
 pub mod coin_up;
+pub mod mosaic;
 pub mod points;
+pub mod springboard;
 pub mod tile_bounce;
+pub mod timeline;
 
 const ONE_HALF: i32fx8 = i32fx8::wrapping_from(1).div(i32fx8::wrapping_from(2));
 
@@ -56,6 +63,9 @@ pub enum Effect {
     TileBounce,
     CoinUp,
     Points,
+    Springboard,
+    BrickDebris,
+    Mosaic,
 }
 
 pub struct AnimationCtx {
@@ -63,52 +73,93 @@ pub struct AnimationCtx {
     pub animation_tick: u32,
 }
 
-pub struct AnimationEffect {
+pub struct AnimationEffect<T: EffectImpl = Effect> {
     tick_start: u32,
-    effect: Effect,
+    effect: T,
 }
 
-impl AnimationEffect {
+impl<T: EffectImpl> AnimationEffect<T> {
+    fn ctx(&self, tick: u32) -> AnimationCtx {
+        AnimationCtx {
+            tick,
+            animation_tick: tick.saturating_sub(self.tick_start),
+        }
+    }
+
     pub fn tick(&mut self, ctx: &TickContext) -> bool {
-        let animation_tick = ctx.tick_count.saturating_sub(self.tick_start);
-        let anim_ctx = AnimationCtx {
-            tick: ctx.tick_count,
-            animation_tick,
-        };
-        self.effect.tick(anim_ctx)
+        self.effect.tick(self.ctx(ctx.tick_count))
     }
 
     pub fn post_tick(&mut self, ctx: &TickContext) {
-        let animation_tick = ctx.tick_count.saturating_sub(self.tick_start);
-        let anim_ctx = AnimationCtx {
-            tick: ctx.tick_count,
-            animation_tick,
-        };
-        self.effect.post_tick(anim_ctx);
+        self.effect.post_tick(self.ctx(ctx.tick_count));
     }
 }
 
+/// Decides which effects go first when the queues fill up
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum EffectPriority {
+    // Purely cosmetic, fine to lose
+    Low,
+    Normal,
+    // Changes the level or the player when it plays
+    High,
+}
+
+struct PendingEffect {
+    start_tick: u32,
+    priority: EffectPriority,
+    effect: Effect,
+}
+
+/// Running totals of effects that didn't play when asked to
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EffectStats {
+    pub dropped: u16,
+    // Counted once per tick an effect is held back by a full active list
+    pub deferred: u16,
+}
+
+const MAX_ACTIVE_EFFECTS: usize = 8;
+const MAX_PENDING_EFFECTS: usize = 6;
+const MAX_TIMELINES: usize = 4;
+
 pub struct EffectsManager {
-    active_effects: FixedBag<AnimationEffect, 8>,
-    pending_effects: FixedQueue<AnimationEffect, 6>,
+    active_effects: FixedBag<AnimationEffect, MAX_ACTIVE_EFFECTS>,
+    pending_effects: FixedBag<PendingEffect, MAX_PENDING_EFFECTS>,
+    // The tick the manager runs next, delays count from here
+    next_tick: u32,
+    stats: EffectStats,
+    timelines: FixedBag<AnimationEffect<Timeline>, MAX_TIMELINES>,
+    // Shared by every coin so they all spin in step
+    coin_spin: Option<AffineHandle>,
 }
 
 impl EffectsManager {
     pub const fn new() -> Self {
         EffectsManager {
             active_effects: FixedBag::new(),
-            pending_effects: FixedQueue::new(),
+            pending_effects: FixedBag::new(),
+            next_tick: 0,
+            stats: EffectStats {
+                dropped: 0,
+                deferred: 0,
+            },
+            timelines: FixedBag::new(),
+            coin_spin: None,
         }
     }
 
     fn reset_internal(&mut self) {
-        for (_, opt) in self.active_effects.iter_mut_opt() {
-            *opt = None;
+        self.active_effects.clear();
+        self.pending_effects.clear();
+        self.stats = EffectStats::default();
+        self.timelines.clear();
+        if self.coin_spin.is_none() {
+            self.coin_spin = OamManager::alloc_affine();
+        }
+        if let Some(spin) = &self.coin_spin {
+            spin.set(Transform::IDENTITY);
         }
-        AFFINE_PARAM_A.index(1).write(i16fx8::from_bits(1 << 8));
-        AFFINE_PARAM_B.index(1).write(i16fx8::from_bits(0));
-        AFFINE_PARAM_C.index(1).write(i16fx8::from_bits(0));
-        AFFINE_PARAM_D.index(1).write(i16fx8::from_bits(1 << 8));
     }
 
     pub fn on_start() {
@@ -117,34 +168,15 @@ impl EffectsManager {
 
     pub fn tick(_tick: TickContext) {
         let manager: &mut EffectsManager = Effects.assume_init();
-        for _ in 0..manager.pending_effects.len() {
-            let Some(effect) = manager.pending_effects.pop() else {
-                break;
-            };
-            if effect.tick_start > 0 {
-                // gba_warning!("Delaying effect by 1 tick: {}", effect.tick_start);
-                manager.pending_effects.push_pop(AnimationEffect {
-                    tick_start: effect.tick_start.saturating_sub(1),
-                    effect: effect.effect,
-                });
-                continue;
-            }
-
-            let anim_effect = AnimationEffect {
-                tick_start: _tick.tick_count,
-                effect: effect.effect,
-            };
-
-            if let Err(effect) = manager.active_effects.push(anim_effect) {
-                // No space, put it back
-                manager.pending_effects.push_pop(effect);
-                break;
-            }
-        }
+        manager.start_due_effects(_tick.tick_count);
+        manager.next_tick = _tick.tick_count.wrapping_add(1);
 
         manager
             .active_effects
             .iter_filter(|effect| effect.tick(&_tick));
+        manager
+            .timelines
+            .iter_filter(|timeline| timeline.tick(&_tick));
     }
 
     pub fn post_tick(_tick: TickContext) {
@@ -154,29 +186,126 @@ impl EffectsManager {
             has_effects = true;
             effect.1.post_tick(&_tick)
         });
+        manager.timelines.iter_mut().for_each(|timeline| {
+            has_effects = true;
+            timeline.1.post_tick(&_tick)
+        });
 
         if has_effects {
             let mod_tick = mod_mask_u32(_tick.tick_count, crate::math::Powers::_16);
-            if mod_tick == 0 {
-                AFFINE_PARAM_A.index(1).write(i16fx8::from_bits(4 << 8));
+            // Squeezing the width down to nothing and back out mirrored reads as a spin
+            let scale_x = if mod_tick == 0 {
+                Some(64)
             } else if mod_tick == 2 || mod_tick == 14 {
-                AFFINE_PARAM_A.index(1).write(i16fx8::from_bits(2 << 8));
+                Some(128)
             } else if mod_tick == 4 || mod_tick == 12 {
-                AFFINE_PARAM_A.index(1).write(i16fx8::from_bits(1 << 8));
+                Some(256)
             } else if mod_tick == 6 || mod_tick == 10 {
-                AFFINE_PARAM_A.index(1).write(-i16fx8::from_bits(2 << 8));
+                Some(-128)
             } else if mod_tick == 8 {
-                AFFINE_PARAM_A.index(1).write(-i16fx8::from_bits(4 << 8));
+                Some(-64)
+            } else {
+                None
+            };
+            if let (Some(scale_x), Some(spin)) = (scale_x, &manager.coin_spin) {
+                spin.set(Transform {
+                    scale_x: i32fx8::from_bits(scale_x),
+                    ..Transform::IDENTITY
+                });
             }
         }
     }
 
-    pub fn add_effect(effect: Effect, delay_ticks: u32) {
+    /// Another reference to the spin shared by the coins
+    pub fn coin_spin() -> Option<AffineHandle> {
+        Effects.assume_init().coin_spin.clone()
+    }
+
+    /// Starts playing the timeline this tick, counted as dropped if there's no room for it
+    pub fn add_timeline(timeline: Timeline) -> Result<usize, Timeline> {
         let manager = Effects.assume_init();
-        manager.pending_effects.push_pop(AnimationEffect {
-            tick_start: delay_ticks,
+        manager
+            .timelines
+            .push(AnimationEffect {
+                // Steps are timed off the frame count, so the start doesn't matter
+                tick_start: 0,
+                effect: timeline,
+            })
+            .map_err(|timeline| {
+                manager.stats.dropped = manager.stats.dropped.saturating_add(1);
+                timeline.effect
+            })
+    }
+
+    /// Queues the effect to start after the delay. When the queue is full a pending effect of lower
+    /// priority is dropped to make room, otherwise the new one is handed back
+    pub fn add_effect(
+        effect: Effect,
+        delay_ticks: u32,
+        priority: EffectPriority,
+    ) -> Result<(), Effect> {
+        let manager = Effects.assume_init();
+        let pending = PendingEffect {
+            start_tick: manager.next_tick.wrapping_add(delay_ticks),
+            priority,
             effect,
-        });
+        };
+        let pending = match manager.pending_effects.push(pending) {
+            Ok(_) => return Ok(()),
+            Err(pending) => pending,
+        };
+
+        manager.stats.dropped = manager.stats.dropped.saturating_add(1);
+        let lowest = manager
+            .pending_effects
+            .iter()
+            .min_by_key(|(_, queued)| queued.priority)
+            .filter(|(_, queued)| queued.priority < priority)
+            .map(|(idx, _)| idx);
+        let Some(lowest) = lowest else {
+            return Err(pending.effect);
+        };
+        gba_warning!("Effect queue full, dropped a lower priority effect");
+        manager.pending_effects.remove(lowest);
+        // A slot was just freed
+        let _ = manager.pending_effects.push(pending);
+        Ok(())
+    }
+
+    /// Moves pending effects whose start tick has come into the active list, highest priority first
+    fn start_due_effects(&mut self, tick: u32) {
+        loop {
+            let due = self
+                .pending_effects
+                .iter()
+                .filter(|(_, pending)| pending.start_tick <= tick)
+                .max_by_key(|(_, pending)| (pending.priority, u32::MAX - pending.start_tick))
+                .map(|(idx, _)| idx);
+            let Some(due) = due else {
+                return;
+            };
+
+            if self.active_effects.is_full() {
+                let waiting = self
+                    .pending_effects
+                    .iter()
+                    .filter(|(_, pending)| pending.start_tick <= tick)
+                    .count() as u16;
+                self.stats.deferred = self.stats.deferred.saturating_add(waiting);
+                return;
+            }
+
+            if let Some(pending) = self.pending_effects.take(due) {
+                let _ = self.active_effects.push(AnimationEffect {
+                    tick_start: tick,
+                    effect: pending.effect,
+                });
+            }
+        }
+    }
+
+    pub fn stats() -> EffectStats {
+        Effects.assume_init().stats
     }
 }
 
@@ -0,0 +1,66 @@
+use gba::prelude::{MOSAIC, Mosaic as MosaicSize};
+
+use crate::{
+    effects::{AnimationCtx, Effect, EffectImpl},
+    oam::OamManager,
+};
+
+// Block sizes are 4 bits each
+const MAX_SIZE: u32 = 15;
+
+/// Pixelates BG2 and every sprite, growing the blocks for half the frames and shrinking them back.
+/// Both go back to normal when the effect is dropped, even if it's cleared before finishing
+pub struct Mosaic {
+    frames: u32,
+}
+
+impl Mosaic {
+    pub fn new(frames: u32) -> Self {
+        Mosaic {
+            frames: frames.max(2),
+        }
+    }
+
+    pub fn as_effect(self) -> Effect {
+        Effect::Mosaic(self)
+    }
+
+    /// Extra pixels per block, 0 is no mosaic at all
+    fn size(&self, animation_tick: u32) -> u16 {
+        let half = self.frames / 2;
+        let from_peak = half.abs_diff(animation_tick);
+        (MAX_SIZE * half.saturating_sub(from_peak) / half) as u16
+    }
+}
+
+impl EffectImpl for Mosaic {
+    fn tick(&mut self, ctx: AnimationCtx) -> bool {
+        if ctx.animation_tick >= self.frames {
+            return false;
+        }
+
+        if ctx.animation_tick == 0 {
+            OamManager::set_mosaic(true);
+        }
+
+        return true;
+    }
+
+    fn post_tick(&mut self, ctx: AnimationCtx) {
+        let size = self.size(ctx.animation_tick);
+        MOSAIC.write(
+            MosaicSize::new()
+                .with_bg_h_extra(size)
+                .with_bg_v_extra(size)
+                .with_obj_h_extra(size)
+                .with_obj_v_extra(size),
+        );
+    }
+}
+
+impl Drop for Mosaic {
+    fn drop(&mut self) {
+        OamManager::set_mosaic(false);
+        MOSAIC.write(MosaicSize::new());
+    }
+}
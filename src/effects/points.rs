@@ -3,6 +3,7 @@ use gba::prelude::*;
 use crate::{
     assets::POINT_TILE_IDX_START,
     effects::{AnimationCtx, Effect, EffectImpl, tile_to_screenspace},
+    oam::{OamHandle, OamManager},
     screen::ScreenManager,
 };
 
@@ -18,6 +19,7 @@ enum PointsAnimationTileIdx {
     TrailingZero = 5,
     OneUpLeft = 6,
     OneUpRight = 7,
+    TrailingZeros = 8,
 }
 
 impl PointsAnimationTileIdx {
@@ -27,21 +29,66 @@ impl PointsAnimationTileIdx {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ScoreAmount {
     OneHundred,
     TwoHundred,
     FourHundred,
     FiveHundred,
     EightHundred,
+    OneThousand,
+    TwoThousand,
+    FourThousand,
+    FiveThousand,
+    EightThousand,
     OneUp,
 }
 
+impl ScoreAmount {
+    /// Points added to the top bar, a 1-up is worth a life instead
+    pub fn value(self) -> u32 {
+        match self {
+            ScoreAmount::OneHundred => 100,
+            ScoreAmount::TwoHundred => 200,
+            ScoreAmount::FourHundred => 400,
+            ScoreAmount::FiveHundred => 500,
+            ScoreAmount::EightHundred => 800,
+            ScoreAmount::OneThousand => 1000,
+            ScoreAmount::TwoThousand => 2000,
+            ScoreAmount::FourThousand => 4000,
+            ScoreAmount::FiveThousand => 5000,
+            ScoreAmount::EightThousand => 8000,
+            ScoreAmount::OneUp => 0,
+        }
+    }
+
+    /// Score for the nth consecutive kill of a chain (starting at 0), following the SMB ladder
+    /// 100, 200, 400, 500, 800, 1000, 2000, 4000, 5000, 8000 and capping out at a 1-up
+    pub fn from_chain(idx: u8) -> Self {
+        match idx {
+            0 => ScoreAmount::OneHundred,
+            1 => ScoreAmount::TwoHundred,
+            2 => ScoreAmount::FourHundred,
+            3 => ScoreAmount::FiveHundred,
+            4 => ScoreAmount::EightHundred,
+            5 => ScoreAmount::OneThousand,
+            6 => ScoreAmount::TwoThousand,
+            7 => ScoreAmount::FourThousand,
+            8 => ScoreAmount::FiveThousand,
+            9 => ScoreAmount::EightThousand,
+            _ => ScoreAmount::OneUp,
+        }
+    }
+}
+
 pub struct Points {
     row: usize,
     col: usize,
     amount: ScoreAmount,
     otr_left: ObjAttr,
     otr_right: ObjAttr,
+    oam_left: Option<OamHandle>,
+    oam_right: Option<OamHandle>,
 }
 
 impl Points {
@@ -52,6 +99,8 @@ impl Points {
             amount,
             otr_left: ObjAttr::default(),
             otr_right: ObjAttr::default(),
+            oam_left: None,
+            oam_right: None,
         }
     }
 
@@ -81,6 +130,26 @@ impl Points {
                 PointsAnimationTileIdx::Eighty,
                 PointsAnimationTileIdx::TrailingZero,
             ),
+            ScoreAmount::OneThousand => (
+                PointsAnimationTileIdx::Ten,
+                PointsAnimationTileIdx::TrailingZeros,
+            ),
+            ScoreAmount::TwoThousand => (
+                PointsAnimationTileIdx::Twenty,
+                PointsAnimationTileIdx::TrailingZeros,
+            ),
+            ScoreAmount::FourThousand => (
+                PointsAnimationTileIdx::Fourty,
+                PointsAnimationTileIdx::TrailingZeros,
+            ),
+            ScoreAmount::FiveThousand => (
+                PointsAnimationTileIdx::Fifty,
+                PointsAnimationTileIdx::TrailingZeros,
+            ),
+            ScoreAmount::EightThousand => (
+                PointsAnimationTileIdx::Eighty,
+                PointsAnimationTileIdx::TrailingZeros,
+            ),
             ScoreAmount::OneUp => (
                 PointsAnimationTileIdx::OneUpLeft,
                 PointsAnimationTileIdx::OneUpRight,
@@ -92,8 +161,6 @@ impl Points {
 impl EffectImpl for Points {
     fn tick(&mut self, ctx: AnimationCtx) -> bool {
         if ctx.animation_tick >= 16 {
-            OBJ_ATTR_ALL.index(3).write(ObjAttr::default());
-            OBJ_ATTR_ALL.index(4).write(ObjAttr::default());
             return false;
         }
 
@@ -120,6 +187,8 @@ impl EffectImpl for Points {
 
             self.otr_left = otr_left;
             self.otr_right = otr_right;
+            self.oam_left = OamManager::alloc();
+            self.oam_right = OamManager::alloc();
         }
 
         return true;
@@ -138,7 +207,11 @@ impl EffectImpl for Points {
             .set_x(x.saturating_add(8).clamp(-60, 240) as u16);
         self.otr_right.set_y(y);
 
-        OBJ_ATTR_ALL.index(3).write(self.otr_left);
-        OBJ_ATTR_ALL.index(4).write(self.otr_right);
+        if let Some(oam) = &self.oam_left {
+            oam.write(self.otr_left);
+        }
+        if let Some(oam) = &self.oam_right {
+            oam.write(self.otr_right);
+        }
     }
 }
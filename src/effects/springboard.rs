@@ -0,0 +1,117 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::SPRINGBOARD_TILE_IDX_START,
+    effects::{AnimationCtx, Effect, EffectImpl, tile_to_screenspace},
+    level_manager::{clear_tile, draw_tile},
+    levels::shared::SPRINGBOARD,
+    oam::{OamHandle, OamManager},
+    player::PlayerManager,
+    screen::ScreenManager,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SpringboardAnimationTileIdx {
+    Rest = 0,
+    Half = 1 * 4,
+    Squashed = 2 * 4,
+}
+
+impl SpringboardAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (SPRINGBOARD_TILE_IDX_START as u16 + self as u16) * 2
+    }
+
+    // How far the top of the board sits below the top of the tile
+    fn depth(self) -> i32 {
+        match self {
+            SpringboardAnimationTileIdx::Rest => 0,
+            SpringboardAnimationTileIdx::Half => 4,
+            SpringboardAnimationTileIdx::Squashed => 8,
+        }
+    }
+}
+
+// Squash down, hold, spring back up and launch once fully extended
+const HALF_DOWN_END: u32 = 4;
+const SQUASHED_END: u32 = 10;
+const LAUNCH_TICK: u32 = 14;
+
+pub struct Springboard {
+    row: usize,
+    col: usize,
+    otr: ObjAttr,
+    oam: Option<OamHandle>,
+}
+
+impl Springboard {
+    pub fn new(row: usize, col: usize) -> Self {
+        Springboard {
+            row,
+            col,
+            otr: ObjAttr::default(),
+            oam: None,
+        }
+    }
+
+    pub fn as_effect(self) -> Effect {
+        Effect::Springboard(self)
+    }
+
+    fn frame(animation_tick: u32) -> SpringboardAnimationTileIdx {
+        if animation_tick < HALF_DOWN_END {
+            SpringboardAnimationTileIdx::Half
+        } else if animation_tick < SQUASHED_END {
+            SpringboardAnimationTileIdx::Squashed
+        } else if animation_tick < LAUNCH_TICK {
+            SpringboardAnimationTileIdx::Half
+        } else {
+            SpringboardAnimationTileIdx::Rest
+        }
+    }
+}
+
+impl EffectImpl for Springboard {
+    fn tick(&mut self, ctx: AnimationCtx) -> bool {
+        if ctx.animation_tick >= LAUNCH_TICK {
+            PlayerManager::spring_launch();
+            draw_tile(self.row, self.col, SPRINGBOARD);
+            return false;
+        }
+
+        if ctx.animation_tick == 0 {
+            let mut otr = ObjAttr::new();
+            otr.set_style(ObjDisplayStyle::Normal);
+            otr.0 = otr
+                .0
+                .with_shape(ObjShape::Square)
+                .with_mode(ObjEffectMode::Normal)
+                .with_bpp8(true);
+            otr.1 = otr.1.with_size(1);
+            otr.2 = otr.2.with_priority(0).with_palbank(0);
+            self.otr = otr;
+            self.oam = OamManager::alloc();
+            clear_tile(self.row, self.col);
+        }
+
+        // The rider sinks with the top of the board
+        PlayerManager::spring_compress(Self::frame(ctx.animation_tick).depth());
+        return true;
+    }
+
+    fn post_tick(&mut self, ctx: AnimationCtx) {
+        let screen = ScreenManager::get_screen_info();
+        let (x, y) = tile_to_screenspace(self.row, self.col, &screen);
+
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(0, 256) as u16);
+        self.otr.2 = self
+            .otr
+            .2
+            .with_tile_id(Self::frame(ctx.animation_tick).tile_id());
+        if let Some(oam) = &self.oam {
+            oam.write(self.otr);
+        }
+    }
+}
@@ -3,8 +3,11 @@ use gba::prelude::*;
 use crate::{
     assets::{BRICK_IDX_START, USED_BLOCK_IDX_START},
     effects::{AnimationCtx, Effect, EffectImpl, tile_to_screenspace},
+    enemies::EnemyManager,
+    items::ItemManager,
     level_manager::{clear_tile, draw_tile},
     levels::shared::{BRICK, QUESTION_BLOCK_USED, Tile},
+    oam::{OamHandle, OamManager},
     screen::ScreenManager,
 };
 
@@ -34,6 +37,7 @@ pub struct TileBounce {
     col: usize,
     tile: BounceEffectTile,
     otr: ObjAttr,
+    oam: Option<OamHandle>,
 }
 
 impl TileBounce {
@@ -43,6 +47,7 @@ impl TileBounce {
             col,
             tile,
             otr: ObjAttr::default(),
+            oam: None,
         }
     }
 
@@ -55,7 +60,6 @@ impl EffectImpl for TileBounce {
     fn tick(&mut self, ctx: AnimationCtx) -> bool {
         if ctx.animation_tick >= 8 {
             draw_tile(self.row, self.col, self.tile.tile());
-            OBJ_ATTR_ALL.index(1).write(ObjAttr::default());
             return false;
         }
 
@@ -63,20 +67,24 @@ impl EffectImpl for TileBounce {
             let mut otr = ObjAttr::new();
             otr.set_x(32);
             otr.set_y(32);
-            otr.set_style(ObjDisplayStyle::Affine);
+            otr.set_style(ObjDisplayStyle::Normal);
             otr.0 = otr
                 .0
                 .with_shape(ObjShape::Square)
                 .with_mode(ObjEffectMode::Normal)
                 .with_bpp8(true);
-            otr.1 = otr.1.with_size(1).with_affine_index(0);
+            otr.1 = otr.1.with_size(1);
             otr.2 = otr
                 .2
                 .with_tile_id(self.tile.obj_tile_id())
                 .with_priority(0)
                 .with_palbank(0);
             self.otr = otr;
+            self.oam = OamManager::alloc();
             clear_tile(self.row, self.col);
+            // Anything standing on the block gets hit along with it
+            EnemyManager::bump_from_below(self.row, self.col);
+            ItemManager::bump_from_below(self.row, self.col);
         }
 
         return true;
@@ -101,6 +109,8 @@ impl EffectImpl for TileBounce {
         //     difference,
         //     x
         // );
-        OBJ_ATTR_ALL.index(1).write(self.otr);
+        if let Some(oam) = &self.oam {
+            oam.write(self.otr);
+        }
     }
 }
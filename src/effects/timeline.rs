@@ -0,0 +1,109 @@
+use crate::{
+    effects::{AnimationCtx, AnimationEffect, Effect, EffectImpl},
+    fixed_queue::FixedQueue,
+    gba_warning,
+};
+
+const MAX_STEPS: usize = 6;
+const MAX_PARALLEL: usize = 3;
+
+enum Step {
+    // Effects that start together, the step is over once all of them are
+    Group([Option<Effect>; MAX_PARALLEL]),
+    Wait(u32),
+    Hook(fn(u32), u32),
+}
+
+/// Effects played one step after another, built up front and handed to `EffectsManager::add_timeline`
+pub struct Timeline {
+    steps: FixedQueue<Step, MAX_STEPS>,
+    running: [Option<AnimationEffect>; MAX_PARALLEL],
+    wait_until: u32,
+}
+
+impl Timeline {
+    pub const fn new() -> Self {
+        Timeline {
+            steps: FixedQueue::new(),
+            running: [const { None }; MAX_PARALLEL],
+            wait_until: 0,
+        }
+    }
+
+    /// Plays the effect once everything before it has finished
+    pub fn then(self, effect: Effect) -> Self {
+        self.parallel([effect])
+    }
+
+    /// Plays all the effects at once, the next step waits for the longest of them
+    pub fn parallel<const N: usize>(self, effects: [Effect; N]) -> Self {
+        const { assert!(N <= MAX_PARALLEL, "Timeline group too big") };
+        let mut group = [const { None }; MAX_PARALLEL];
+        for (slot, effect) in group.iter_mut().zip(effects) {
+            *slot = Some(effect);
+        }
+        self.push(Step::Group(group))
+    }
+
+    /// Holds the next step back by this many ticks
+    pub fn wait(self, ticks: u32) -> Self {
+        self.push(Step::Wait(ticks))
+    }
+
+    /// Calls back into game logic once everything before it has finished
+    pub fn on_complete(self, hook: fn(u32), arg: u32) -> Self {
+        self.push(Step::Hook(hook, arg))
+    }
+
+    fn push(mut self, step: Step) -> Self {
+        if self.steps.push(step).is_err() {
+            gba_warning!("Timeline full, dropped a step");
+        }
+        self
+    }
+
+    /// Ticks the running effects, returns true once they've all finished
+    fn tick_running(&mut self, tick: u32) -> bool {
+        let mut is_done = true;
+        for slot in self.running.iter_mut() {
+            if let Some(effect) = slot {
+                if effect.effect.tick(effect.ctx(tick)) {
+                    is_done = false;
+                } else {
+                    *slot = None;
+                }
+            }
+        }
+        is_done
+    }
+}
+
+impl EffectImpl for Timeline {
+    fn tick(&mut self, ctx: AnimationCtx) -> bool {
+        loop {
+            if !self.tick_running(ctx.tick) || self.wait_until > ctx.tick {
+                return true;
+            }
+
+            match self.steps.pop() {
+                None => return false,
+                Some(Step::Group(group)) => {
+                    for (slot, effect) in self.running.iter_mut().zip(group) {
+                        *slot = effect.map(|effect| AnimationEffect {
+                            tick_start: ctx.tick,
+                            effect,
+                        });
+                    }
+                }
+                Some(Step::Wait(ticks)) => self.wait_until = ctx.tick + ticks,
+                Some(Step::Hook(hook, arg)) => hook(arg),
+            }
+        }
+    }
+
+    fn post_tick(&mut self, ctx: AnimationCtx) {
+        for effect in self.running.iter_mut().flatten() {
+            effect.effect.post_tick(effect.ctx(ctx.tick));
+        }
+    }
+}
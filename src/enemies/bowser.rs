@@ -0,0 +1,207 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::BOWSER_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl, bowser_fire::BowserFire, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    player::PlayerManager,
+    rng,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BowserAnimationTileIdx {
+    // 32x32 frames, 16 tiles each
+    MouthClosed = 0,
+    MouthOpen = 1 * 16,
+}
+
+impl BowserAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (BOWSER_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+const PACE_SPEED: i32fx8 = i32fx8::from_bits(128);
+// How far either side of his spawn point he walks before turning around
+const PACE_RANGE: i32 = 24;
+const JUMP_VELOCITY: i32fx8 = i32fx8::from_bits(-1024);
+const JUMP_TICKS: u16 = 180;
+const FIRE_TICKS: u16 = 150;
+// The mouth opens this long before the flame comes out
+const MOUTH_OPEN_TICKS: u16 = 24;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BowserState {
+    Pacing,
+    Defeated,
+}
+
+pub struct Bowser {
+    // The body is the 16x16 at the bottom middle of the 32x32 sprite, so the tile collision works as is
+    body: EntityBody,
+    home_x: i32,
+    state: BowserState,
+    facing_left: bool,
+    jump_ticks: u16,
+    fire_ticks: u16,
+    pending_fire: Option<BowserFire>,
+    otr: ObjAttr,
+}
+
+impl Bowser {
+    /// Spawns Bowser standing on the given row (8px units) at the given column (16px units)
+    pub fn new(row: usize, col: usize) -> Self {
+        let home_x = (col << 4) as i32 + 8;
+        let mut body = EntityBody::new(
+            i32fx8::wrapping_from(home_x),
+            i32fx8::wrapping_from((row << 3) as i32 + 1),
+        );
+        body.vel_x = -PACE_SPEED;
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(2);
+        otr.2 = otr.2.with_priority(0).with_palbank(0);
+
+        Bowser {
+            body,
+            home_x,
+            state: BowserState::Pacing,
+            facing_left: true,
+            jump_ticks: rng::jitter(JUMP_TICKS, 2),
+            fire_ticks: rng::jitter(FIRE_TICKS, 2),
+            pending_fire: None,
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::Bowser(self)
+    }
+
+    fn breathe_fire(&mut self) {
+        let mouth_x = if self.facing_left {
+            self.body.px_x() - 8 - 24
+        } else {
+            self.body.px_x() + 24
+        };
+        let mouth_y = self.body.px_y() - 8;
+        // Flames drift towards the height the player was at when they were breathed
+        let target_y = PlayerManager::hitbox().map_or(mouth_y, |player| player.y + 4);
+        self.pending_fire = Some(BowserFire::new(
+            mouth_x,
+            mouth_y,
+            target_y,
+            self.facing_left,
+        ));
+    }
+}
+
+impl EnemyImpl for Bowser {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        if self.state == BowserState::Defeated {
+            self.body.apply_gravity();
+            return !self.body.step_free();
+        }
+
+        if let Some(player) = PlayerManager::hitbox() {
+            self.facing_left = player.x + 8 < self.body.px_x() + 8;
+        }
+
+        let x = self.body.px_x();
+        if x <= self.home_x - PACE_RANGE {
+            self.body.vel_x = PACE_SPEED;
+        } else if x >= self.home_x + PACE_RANGE {
+            self.body.vel_x = -PACE_SPEED;
+        }
+
+        self.body.apply_gravity();
+        let collision = self.body.step();
+        // Falls out of the level once the bridge under him is gone
+        if collision.fell_out {
+            return false;
+        }
+        if collision.hit_wall {
+            self.body.vel_x = -self.body.vel_x;
+        }
+
+        if collision.on_ground {
+            self.jump_ticks = self.jump_ticks.saturating_sub(1);
+            if self.jump_ticks == 0 {
+                self.body.vel_y = JUMP_VELOCITY;
+                self.jump_ticks = rng::jitter(JUMP_TICKS, 2);
+            }
+        }
+
+        self.fire_ticks = self.fire_ticks.saturating_sub(1);
+        if self.fire_ticks == 0 {
+            self.breathe_fire();
+            self.fire_ticks = rng::jitter(FIRE_TICKS, 2);
+        }
+
+        true
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            BowserState::Pacing => Some(Hitbox {
+                x: self.body.px_x() - 6,
+                y: self.body.px_y() - 14,
+                w: 28,
+                h: 30,
+            }),
+            BowserState::Defeated => None,
+        }
+    }
+
+    fn on_player_contact(&mut self, _player: &Hitbox, _player_falling: bool) -> ContactResult {
+        // Far too big to stomp
+        ContactResult::HurtPlayer
+    }
+
+    fn knock_out(&mut self) {
+        self.state = BowserState::Defeated;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn can_knock_out(&self) -> bool {
+        false
+    }
+
+    fn take_spawn(&mut self) -> Option<Enemy> {
+        self.pending_fire.take().map(BowserFire::as_enemy)
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        let tile = if self.fire_ticks < MOUTH_OPEN_TICKS {
+            BowserAnimationTileIdx::MouthOpen
+        } else {
+            BowserAnimationTileIdx::MouthClosed
+        };
+        // The sprite hangs 8px either side of and 16px above the body
+        self.otr.set_x((x - 8).clamp(-60, 240) as u16);
+        self.otr.set_y((y - 16).clamp(-32, 160) as u16);
+        self.otr.2 = self.otr.2.with_tile_id(tile.tile_id());
+        // Art faces left, mirror it when the player is to his right
+        self.otr.1 = self
+            .otr
+            .1
+            .with_hflip(!self.facing_left)
+            .with_vflip(self.state == BowserState::Defeated);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
@@ -0,0 +1,103 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::BOWSER_FIRE_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+const FIRE_SPEED: i32fx8 = i32fx8::from_bits(384);
+// Vertical drift towards the target height, per tick
+const DRIFT_SPEED: i32fx8 = i32fx8::from_bits(64);
+
+pub struct BowserFire {
+    // Top left of the 32x8 flame
+    body: EntityBody,
+    target_y: i32,
+    anim_tick: u8,
+    otr: ObjAttr,
+}
+
+impl BowserFire {
+    /// Flame starting with its top left at (x, y) in world pixels, flying straight and easing to target_y
+    pub fn new(x: i32, y: i32, target_y: i32, moving_left: bool) -> Self {
+        let mut body = EntityBody::new(i32fx8::wrapping_from(x), i32fx8::wrapping_from(y));
+        body.vel_x = if moving_left { -FIRE_SPEED } else { FIRE_SPEED };
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Horizontal)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(1).with_hflip(!moving_left);
+        otr.2 = otr
+            .2
+            .with_tile_id(BOWSER_FIRE_TILE_IDX_START as u16 * 2)
+            .with_priority(0)
+            .with_palbank(0);
+
+        BowserFire {
+            body,
+            target_y,
+            anim_tick: 0,
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::BowserFire(self)
+    }
+}
+
+impl EnemyImpl for BowserFire {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        self.anim_tick = self.anim_tick.wrapping_add(1);
+        let y = self.body.px_y();
+        self.body.vel_y = if y < self.target_y {
+            DRIFT_SPEED
+        } else if y > self.target_y {
+            -DRIFT_SPEED
+        } else {
+            i32fx8::default()
+        };
+        // Flames fly through the level, they only go away once offscreen
+        !self.body.step_free()
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        Some(Hitbox {
+            x: self.body.px_x() + 4,
+            y: self.body.px_y() + 2,
+            w: 24,
+            h: 4,
+        })
+    }
+
+    fn on_player_contact(&mut self, _player: &Hitbox, _player_falling: bool) -> ContactResult {
+        ContactResult::HurtPlayer
+    }
+
+    fn knock_out(&mut self) {}
+
+    fn can_knock_out(&self) -> bool {
+        false
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-8, 160) as u16);
+        // Flicker the flame by flipping it
+        self.otr.1 = self.otr.1.with_vflip(self.anim_tick & 0b100 != 0);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
@@ -0,0 +1,114 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::BULLET_BILL_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl, is_stomp, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+const FLY_SPEED: i32fx8 = i32fx8::from_bits(384);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BulletBillState {
+    Flying,
+    KnockedOut,
+}
+
+pub struct BulletBill {
+    body: EntityBody,
+    state: BulletBillState,
+    otr: ObjAttr,
+}
+
+impl BulletBill {
+    /// Bullet Bill leaving the blaster whose top is on row (8px units) at col (16px units)
+    pub fn new(row: usize, col: usize, moving_left: bool) -> Self {
+        let mut body = EntityBody::new(
+            i32fx8::wrapping_from((col << 4) as i32),
+            i32fx8::wrapping_from((row << 3) as i32),
+        );
+        body.vel_x = if moving_left { -FLY_SPEED } else { FLY_SPEED };
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        // Art faces left
+        otr.1 = otr.1.with_size(1).with_hflip(!moving_left);
+        otr.2 = otr
+            .2
+            .with_tile_id(BULLET_BILL_TILE_IDX_START as u16 * 2)
+            .with_priority(0)
+            .with_palbank(0);
+
+        BulletBill {
+            body,
+            state: BulletBillState::Flying,
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::BulletBill(self)
+    }
+}
+
+impl EnemyImpl for BulletBill {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        // Flies straight through everything until it leaves the screen
+        if self.state == BulletBillState::KnockedOut {
+            self.body.apply_gravity();
+        }
+        !self.body.step_free()
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            BulletBillState::Flying => Some(Hitbox {
+                x: self.body.px_x() + 1,
+                y: self.body.px_y() + 2,
+                w: 14,
+                h: 12,
+            }),
+            BulletBillState::KnockedOut => None,
+        }
+    }
+
+    fn on_player_contact(&mut self, player: &Hitbox, player_falling: bool) -> ContactResult {
+        let Some(hitbox) = self.hitbox() else {
+            return ContactResult::Ignored;
+        };
+        if !is_stomp(player, player_falling, &hitbox) {
+            return ContactResult::HurtPlayer;
+        }
+
+        self.knock_out();
+        ContactResult::Stomped
+    }
+
+    fn knock_out(&mut self) {
+        self.state = BulletBillState::KnockedOut;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-16, 160) as u16);
+        self.otr.1 = self
+            .otr
+            .1
+            .with_vflip(self.state == BulletBillState::KnockedOut);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
@@ -0,0 +1,132 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::CHEEP_CHEEP_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl, is_stomp, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CheepCheepAnimationTileIdx {
+    FinsUp = 0,
+    FinsDown = 1 * 4,
+}
+
+impl CheepCheepAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (CHEEP_CHEEP_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+// Much floatier than the regular gravity so the leaps hang in the air
+const LEAP_GRAVITY: i32fx8 = i32fx8::from_bits(32);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CheepCheepState {
+    Leaping,
+    KnockedOut,
+}
+
+pub struct CheepCheep {
+    body: EntityBody,
+    state: CheepCheepState,
+    anim_tick: u8,
+    otr: ObjAttr,
+}
+
+impl CheepCheep {
+    /// Cheep-cheep leaping from (x, y) in world pixels with the given starting velocity
+    pub fn leap(x: i32, y: i32, vel_x: i32fx8, vel_y: i32fx8) -> Self {
+        let mut body = EntityBody::new(i32fx8::wrapping_from(x), i32fx8::wrapping_from(y));
+        body.vel_x = vel_x;
+        body.vel_y = vel_y;
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        // Art faces left
+        otr.1 = otr.1.with_size(1).with_hflip(!body.is_moving_left());
+        otr.2 = otr.2.with_priority(0).with_palbank(0);
+
+        CheepCheep {
+            body,
+            state: CheepCheepState::Leaping,
+            anim_tick: 0,
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::CheepCheep(self)
+    }
+}
+
+impl EnemyImpl for CheepCheep {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        match self.state {
+            CheepCheepState::Leaping => self.body.vel_y = self.body.vel_y.add(LEAP_GRAVITY),
+            CheepCheepState::KnockedOut => self.body.apply_gravity(),
+        }
+        self.anim_tick = self.anim_tick.wrapping_add(1);
+        // Leaps ignore the level, the fish drops back below the screen when it's done
+        !self.body.step_free()
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            CheepCheepState::Leaping => Some(Hitbox {
+                x: self.body.px_x() + 1,
+                y: self.body.px_y() + 2,
+                w: 14,
+                h: 12,
+            }),
+            CheepCheepState::KnockedOut => None,
+        }
+    }
+
+    fn on_player_contact(&mut self, player: &Hitbox, player_falling: bool) -> ContactResult {
+        let Some(hitbox) = self.hitbox() else {
+            return ContactResult::Ignored;
+        };
+        if !is_stomp(player, player_falling, &hitbox) {
+            return ContactResult::HurtPlayer;
+        }
+
+        self.knock_out();
+        ContactResult::Stomped
+    }
+
+    fn knock_out(&mut self) {
+        self.state = CheepCheepState::KnockedOut;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        let tile = if self.anim_tick & 0b1000 != 0 {
+            CheepCheepAnimationTileIdx::FinsDown
+        } else {
+            CheepCheepAnimationTileIdx::FinsUp
+        };
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-16, 160) as u16);
+        self.otr.2 = self.otr.2.with_tile_id(tile.tile_id());
+        self.otr.1 = self
+            .otr
+            .1
+            .with_vflip(self.state == CheepCheepState::KnockedOut);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
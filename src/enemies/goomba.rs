@@ -0,0 +1,155 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::GOOMBA_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl, is_stomp, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GoombaAnimationTileIdx {
+    Walking = 0,
+    Flat = 1 * 4,
+}
+
+impl GoombaAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (GOOMBA_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+const WALK_SPEED: i32fx8 = i32fx8::from_bits(128);
+// How long the squished sprite stays on screen
+const FLAT_TICKS: u8 = 30;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GoombaState {
+    Walking,
+    Flat { ticks_left: u8 },
+    KnockedOut,
+}
+
+pub struct Goomba {
+    body: EntityBody,
+    state: GoombaState,
+    anim_tick: u8,
+    otr: ObjAttr,
+}
+
+impl Goomba {
+    /// Spawns a goomba standing on the given row (8px units) at the given column (16px units), walking left
+    pub fn new(row: usize, col: usize) -> Self {
+        let mut body = EntityBody::new(
+            i32fx8::wrapping_from((col << 4) as i32),
+            i32fx8::wrapping_from((row << 3) as i32 + 1),
+        );
+        body.vel_x = -WALK_SPEED;
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(1);
+        otr.2 = otr
+            .2
+            .with_tile_id(GoombaAnimationTileIdx::Walking.tile_id())
+            .with_priority(0)
+            .with_palbank(0);
+
+        Goomba {
+            body,
+            state: GoombaState::Walking,
+            anim_tick: 0,
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::Goomba(self)
+    }
+}
+
+impl EnemyImpl for Goomba {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        match self.state {
+            GoombaState::Flat { ticks_left } => {
+                if ticks_left == 0 {
+                    return false;
+                }
+                self.state = GoombaState::Flat {
+                    ticks_left: ticks_left - 1,
+                };
+                true
+            }
+            GoombaState::Walking => {
+                self.body.apply_gravity();
+                let collision = self.body.step();
+                if collision.fell_out {
+                    return false;
+                }
+                if collision.hit_wall {
+                    self.body.vel_x = -self.body.vel_x;
+                }
+                self.anim_tick = self.anim_tick.wrapping_add(1);
+                true
+            }
+            GoombaState::KnockedOut => {
+                self.body.apply_gravity();
+                !self.body.step_free()
+            }
+        }
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            GoombaState::Walking => Some(self.body.hitbox()),
+            GoombaState::Flat { .. } | GoombaState::KnockedOut => None,
+        }
+    }
+
+    fn on_player_contact(&mut self, player: &Hitbox, player_falling: bool) -> ContactResult {
+        if !is_stomp(player, player_falling, &self.body.hitbox()) {
+            return ContactResult::HurtPlayer;
+        }
+
+        self.state = GoombaState::Flat {
+            ticks_left: FLAT_TICKS,
+        };
+        self.body.vel_x = i32fx8::default();
+        self.otr.2 = self
+            .otr
+            .2
+            .with_tile_id(GoombaAnimationTileIdx::Flat.tile_id());
+        ContactResult::Stomped
+    }
+
+    fn knock_out(&mut self) {
+        self.state = GoombaState::KnockedOut;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-16, 160) as u16);
+        // SMB walks the goomba by mirroring the same frame back and forth
+        let flip = self.state == GoombaState::Walking && self.anim_tick & 0b1000 != 0;
+        self.otr.1 = self
+            .otr
+            .1
+            .with_hflip(flip)
+            .with_vflip(self.state == GoombaState::KnockedOut);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
@@ -0,0 +1,230 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::HAMMER_BRO_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl, is_stomp, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    gba_warning,
+    oam::OamManager,
+    player::PlayerManager,
+    projectiles::{Projectile, ProjectileManager},
+    rng,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum HammerBroAnimationTileIdx {
+    // 16x32 frames, 8 tiles each
+    Walking = 0,
+    Throwing = 1 * 8,
+}
+
+impl HammerBroAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (HAMMER_BRO_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+const PACE_SPEED: i32fx8 = i32fx8::from_bits(96);
+// How far either side of his spawn point he walks before turning around
+const PACE_RANGE: i32 = 16;
+// Enough to clear a brick tier 4 blocks up and land on top of it
+const TIER_JUMP: i32fx8 = i32fx8::from_bits(-2432);
+// Small hop before dropping through the tier he's standing on
+const DROP_HOP: i32fx8 = i32fx8::from_bits(-768);
+const JUMP_TICKS: u16 = 150;
+const THROW_TICKS: u16 = 50;
+// The arm is raised this long before the hammer leaves it
+const WIND_UP_TICKS: u16 = 12;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum HammerBroState {
+    Walking,
+    // Falling through the tier he jumped down from, tiles are ignored until his top passes below_y
+    DroppingThrough { below_y: i32 },
+    KnockedOut,
+}
+
+pub struct HammerBro {
+    // The body is the bottom 16x16 of the 16x32 sprite, so the tile collision works as is
+    body: EntityBody,
+    home_x: i32,
+    // Where he spawned, the lowest tier he'll drop to
+    home_y: i32,
+    state: HammerBroState,
+    facing_left: bool,
+    jump_ticks: u16,
+    throw_ticks: u16,
+    otr: ObjAttr,
+}
+
+impl HammerBro {
+    /// Spawns a hammer bro standing on the given row (8px units) at the given column (16px units)
+    pub fn new(row: usize, col: usize) -> Self {
+        let home_x = (col << 4) as i32;
+        let home_y = (row << 3) as i32 + 1;
+        let mut body =
+            EntityBody::new(i32fx8::wrapping_from(home_x), i32fx8::wrapping_from(home_y));
+        body.vel_x = -PACE_SPEED;
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Vertical)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(2);
+        otr.2 = otr.2.with_priority(0).with_palbank(0);
+
+        HammerBro {
+            body,
+            home_x,
+            home_y,
+            state: HammerBroState::Walking,
+            facing_left: true,
+            jump_ticks: rng::jitter(JUMP_TICKS, 2),
+            throw_ticks: rng::jitter(THROW_TICKS, 2),
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::HammerBro(self)
+    }
+
+    /// Jumps up to the next tier, or from anything above his spawn tier sometimes drops down instead
+    fn jump(&mut self) {
+        let can_drop = self.body.px_y() < self.home_y;
+        if can_drop && rng::next_bool() {
+            self.body.vel_y = DROP_HOP;
+            self.state = HammerBroState::DroppingThrough {
+                below_y: self.body.px_y() + 16,
+            };
+        } else {
+            self.body.vel_y = TIER_JUMP;
+        }
+    }
+
+    fn throw_hammer(&mut self) {
+        let x = if self.facing_left {
+            self.body.px_x()
+        } else {
+            self.body.px_x() + 8
+        };
+        let hammer = Projectile::hammer(x, self.body.px_y() - 14, self.facing_left);
+        if ProjectileManager::spawn(hammer).is_err() {
+            gba_warning!("Projectile pool full, dropped hammer");
+        }
+    }
+}
+
+impl EnemyImpl for HammerBro {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        self.body.apply_gravity();
+        match self.state {
+            HammerBroState::KnockedOut => return !self.body.step_free(),
+            HammerBroState::DroppingThrough { below_y } => {
+                if self.body.step_free() {
+                    return false;
+                }
+                if self.body.px_y() > below_y {
+                    self.state = HammerBroState::Walking;
+                }
+                return true;
+            }
+            HammerBroState::Walking => {}
+        }
+
+        if let Some(player) = PlayerManager::hitbox() {
+            self.facing_left = player.x < self.body.px_x();
+        }
+
+        let x = self.body.px_x();
+        if x <= self.home_x - PACE_RANGE {
+            self.body.vel_x = PACE_SPEED;
+        } else if x >= self.home_x + PACE_RANGE {
+            self.body.vel_x = -PACE_SPEED;
+        }
+
+        let collision = self.body.step();
+        if collision.fell_out {
+            return false;
+        }
+        if collision.hit_wall {
+            self.body.vel_x = -self.body.vel_x;
+        }
+
+        if collision.on_ground {
+            self.jump_ticks = self.jump_ticks.saturating_sub(1);
+            if self.jump_ticks == 0 {
+                self.jump();
+                self.jump_ticks = rng::jitter(JUMP_TICKS, 2);
+            }
+        }
+
+        self.throw_ticks = self.throw_ticks.saturating_sub(1);
+        if self.throw_ticks == 0 {
+            self.throw_hammer();
+            self.throw_ticks = rng::jitter(THROW_TICKS, 2);
+        }
+
+        true
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            HammerBroState::Walking | HammerBroState::DroppingThrough { .. } => Some(Hitbox {
+                x: self.body.px_x() + 2,
+                y: self.body.px_y() - 12,
+                w: 12,
+                h: 28,
+            }),
+            HammerBroState::KnockedOut => None,
+        }
+    }
+
+    fn on_player_contact(&mut self, player: &Hitbox, player_falling: bool) -> ContactResult {
+        let Some(hitbox) = self.hitbox() else {
+            return ContactResult::Ignored;
+        };
+        if !is_stomp(player, player_falling, &hitbox) {
+            return ContactResult::HurtPlayer;
+        }
+
+        // A stomp flips him straight off the level instead of squashing him
+        self.knock_out();
+        ContactResult::Stomped
+    }
+
+    fn knock_out(&mut self) {
+        self.state = HammerBroState::KnockedOut;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        let tile = if self.throw_ticks < WIND_UP_TICKS {
+            HammerBroAnimationTileIdx::Throwing
+        } else {
+            HammerBroAnimationTileIdx::Walking
+        };
+        // The sprite hangs 16px above the body
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y((y - 16).clamp(-32, 160) as u16);
+        self.otr.2 = self.otr.2.with_tile_id(tile.tile_id());
+        // Art faces left, mirror it when the player is to his right
+        self.otr.1 = self
+            .otr
+            .1
+            .with_hflip(!self.facing_left)
+            .with_vflip(self.state == HammerBroState::KnockedOut);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
@@ -0,0 +1,244 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::KOOPA_TILE_IDX_START,
+    effects::points::ScoreAmount,
+    enemies::{ContactResult, Enemy, EnemyImpl, is_stomp, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    scoring,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KoopaAnimationTileIdx {
+    // Walking frames are 16x32, the shell frames are 16x16
+    Walking1 = 0,
+    Walking2 = 1 * 8,
+    Shell = 2 * 8,
+    ShellWaking = 2 * 8 + 4,
+}
+
+impl KoopaAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (KOOPA_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+const WALK_SPEED: i32fx8 = i32fx8::from_bits(128);
+const SLIDE_SPEED: i32fx8 = i32fx8::from_bits(3 << 8);
+// Time spent in the shell before walking again, the last WAKE_WARNING_TICKS of it wiggle
+const SHELL_TICKS: u16 = 300;
+const WAKE_WARNING_TICKS: u16 = 60;
+// Contacts are ignored for a few frames after a stomp or kick so the player can get clear
+const KICK_GRACE_TICKS: u8 = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KoopaState {
+    Walking,
+    Shell { ticks_left: u16 },
+    Sliding,
+    KnockedOut,
+}
+
+pub struct Koopa {
+    body: EntityBody,
+    state: KoopaState,
+    anim_tick: u8,
+    grace_ticks: u8,
+    kill_chain: u8,
+    otr: ObjAttr,
+}
+
+impl Koopa {
+    /// Spawns a koopa standing on the given row (8px units) at the given column (16px units), walking left
+    pub fn new(row: usize, col: usize) -> Self {
+        let mut body = EntityBody::new(
+            i32fx8::wrapping_from((col << 4) as i32),
+            i32fx8::wrapping_from((row << 3) as i32 + 1),
+        );
+        body.vel_x = -WALK_SPEED;
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr.0.with_mode(ObjEffectMode::Normal).with_bpp8(true);
+        otr.2 = otr.2.with_priority(0).with_palbank(0);
+
+        Koopa {
+            body,
+            state: KoopaState::Walking,
+            anim_tick: 0,
+            grace_ticks: 0,
+            kill_chain: 0,
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::Koopa(self)
+    }
+
+    fn enter_shell(&mut self) {
+        self.state = KoopaState::Shell {
+            ticks_left: SHELL_TICKS,
+        };
+        self.body.vel_x = i32fx8::default();
+        self.grace_ticks = KICK_GRACE_TICKS;
+    }
+
+    /// Send the shell sliding away from whichever side the player touched it on
+    fn kick(&mut self, player: &Hitbox) {
+        let player_center = player.x + (player.w >> 1);
+        let shell_center = self.body.px_x() + 8;
+        self.body.vel_x = if player_center < shell_center {
+            SLIDE_SPEED
+        } else {
+            -SLIDE_SPEED
+        };
+        self.state = KoopaState::Sliding;
+        self.kill_chain = 0;
+        self.grace_ticks = KICK_GRACE_TICKS;
+    }
+
+    fn current_tile(&self) -> KoopaAnimationTileIdx {
+        match self.state {
+            KoopaState::Walking => {
+                if self.anim_tick & 0b1000 == 0 {
+                    KoopaAnimationTileIdx::Walking1
+                } else {
+                    KoopaAnimationTileIdx::Walking2
+                }
+            }
+            KoopaState::Shell { ticks_left }
+                if ticks_left < WAKE_WARNING_TICKS && self.anim_tick & 0b100 != 0 =>
+            {
+                KoopaAnimationTileIdx::ShellWaking
+            }
+            KoopaState::Shell { .. } | KoopaState::Sliding | KoopaState::KnockedOut => {
+                KoopaAnimationTileIdx::Shell
+            }
+        }
+    }
+}
+
+impl EnemyImpl for Koopa {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        self.anim_tick = self.anim_tick.wrapping_add(1);
+        self.grace_ticks = self.grace_ticks.saturating_sub(1);
+
+        match self.state {
+            KoopaState::KnockedOut => {
+                self.body.apply_gravity();
+                return !self.body.step_free();
+            }
+            KoopaState::Shell { ticks_left } => {
+                if ticks_left == 0 {
+                    self.state = KoopaState::Walking;
+                    self.body.vel_x = -WALK_SPEED;
+                } else {
+                    self.state = KoopaState::Shell {
+                        ticks_left: ticks_left - 1,
+                    };
+                }
+            }
+            KoopaState::Walking | KoopaState::Sliding => {}
+        }
+
+        self.body.apply_gravity();
+        let collision = self.body.step();
+        if collision.fell_out {
+            return false;
+        }
+        // Both walking koopas and sliding shells bounce back off walls
+        if collision.hit_wall {
+            self.body.vel_x = -self.body.vel_x;
+        }
+        true
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            KoopaState::KnockedOut => None,
+            _ => Some(self.body.hitbox()),
+        }
+    }
+
+    fn on_player_contact(&mut self, player: &Hitbox, player_falling: bool) -> ContactResult {
+        if self.grace_ticks > 0 {
+            return ContactResult::Ignored;
+        }
+
+        let stomped = is_stomp(player, player_falling, &self.body.hitbox());
+        match self.state {
+            KoopaState::Walking | KoopaState::Sliding if stomped => {
+                self.enter_shell();
+                ContactResult::Stomped
+            }
+            KoopaState::Walking | KoopaState::Sliding => ContactResult::HurtPlayer,
+            KoopaState::Shell { .. } => {
+                self.kick(player);
+                scoring::award_above(&self.body, ScoreAmount::FourHundred);
+                if stomped {
+                    ContactResult::Bounced
+                } else {
+                    ContactResult::Ignored
+                }
+            }
+            KoopaState::KnockedOut => ContactResult::Ignored,
+        }
+    }
+
+    fn knock_out(&mut self) {
+        self.state = KoopaState::KnockedOut;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn attack_hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            KoopaState::Sliding => Some(self.body.hitbox()),
+            _ => None,
+        }
+    }
+
+    fn on_attack_kill(&mut self) -> ScoreAmount {
+        let amount = ScoreAmount::from_chain(self.kill_chain);
+        self.kill_chain = self.kill_chain.saturating_add(1);
+        amount
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (mut x, mut y) = self.body.to_screenspace(screen);
+        let tile = self.current_tile();
+
+        if self.state == KoopaState::Walking {
+            // The tall walking frame hangs 16px above the body
+            y -= 16;
+            self.otr.0 = self.otr.0.with_shape(ObjShape::Vertical);
+            self.otr.1 = self.otr.1.with_size(2);
+        } else {
+            self.otr.0 = self.otr.0.with_shape(ObjShape::Square);
+            self.otr.1 = self.otr.1.with_size(1);
+        }
+
+        if tile == KoopaAnimationTileIdx::ShellWaking {
+            x += if self.anim_tick & 0b1000 == 0 { 1 } else { -1 };
+        }
+
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-32, 160) as u16);
+        self.otr.2 = self.otr.2.with_tile_id(tile.tile_id());
+        // Art faces left, mirror it when walking right
+        self.otr.1 = self
+            .otr
+            .1
+            .with_hflip(self.state == KoopaState::Walking && !self.body.is_moving_left())
+            .with_vflip(self.state == KoopaState::KnockedOut);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
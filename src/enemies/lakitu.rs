@@ -0,0 +1,191 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::LAKITU_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl, is_stomp, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    gba_warning,
+    oam::OamManager,
+    player::PlayerManager,
+    projectiles::{Projectile, ProjectileManager},
+    rng,
+    screen::{ScreenInfo, ScreenManager},
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LakituAnimationTileIdx {
+    // 16x32 frames, 8 tiles each
+    Floating = 0,
+    Throwing = 1 * 8,
+}
+
+impl LakituAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (LAKITU_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+// Speeding up towards the player and overshooting is what gives Lakitu his swing
+const ACCEL: i32fx8 = i32fx8::from_bits(16);
+const MAX_SPEED: i32fx8 = i32fx8::from_bits(640);
+// Keeps the whole cloud on screen however fast the camera moves
+const SCREEN_MARGIN_LEFT: i32 = 8;
+const SCREEN_MARGIN_RIGHT: i32 = 240 - 24;
+const THROW_TICKS: u16 = 120;
+// Ducks into the cloud this long before the egg comes out
+const WIND_UP_TICKS: u16 = 16;
+const EGG_THROW: i32fx8 = i32fx8::from_bits(-1024);
+const EGG_SPEED: i32fx8 = i32fx8::from_bits(128);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LakituState {
+    Floating,
+    KnockedOut,
+}
+
+pub struct Lakitu {
+    // The body is the cloud, the bottom 16x16 of the 16x32 sprite
+    body: EntityBody,
+    state: LakituState,
+    throw_ticks: u16,
+    otr: ObjAttr,
+}
+
+impl Lakitu {
+    /// Spawns Lakitu with his cloud on the given row (8px units) at the given column (16px units)
+    pub fn new(row: usize, col: usize) -> Self {
+        let body = EntityBody::new(
+            i32fx8::wrapping_from((col << 4) as i32),
+            i32fx8::wrapping_from((row << 3) as i32),
+        );
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Vertical)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(2);
+        otr.2 = otr.2.with_priority(0).with_palbank(0);
+
+        Lakitu {
+            body,
+            state: LakituState::Floating,
+            throw_ticks: rng::jitter(THROW_TICKS, 1),
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::Lakitu(self)
+    }
+
+    /// Chases the player horizontally while staying inside the camera
+    fn follow(&mut self, player: Option<Hitbox>, screen: &ScreenInfo) {
+        let x = self.body.px_x();
+        if let Some(player) = player {
+            if player.x > x {
+                self.body.vel_x = self.body.vel_x.add(ACCEL).min(MAX_SPEED);
+            } else {
+                self.body.vel_x = self.body.vel_x.sub(ACCEL).max(-MAX_SPEED);
+            }
+        }
+        self.body.x = self.body.x.add(self.body.vel_x);
+
+        let screen_x = screen.affn_x.to_bits() >> 8;
+        let min_x = screen_x + SCREEN_MARGIN_LEFT;
+        let max_x = screen_x + SCREEN_MARGIN_RIGHT;
+        let x = self.body.px_x();
+        if x < min_x {
+            self.body.x = i32fx8::wrapping_from(min_x);
+            self.body.vel_x = self.body.vel_x.max(i32fx8::default());
+        } else if x > max_x {
+            self.body.x = i32fx8::wrapping_from(max_x);
+            self.body.vel_x = self.body.vel_x.min(i32fx8::default());
+        }
+    }
+
+    fn throw_egg(&mut self, player: Option<Hitbox>) {
+        let is_player_right = player.is_some_and(|player| player.x > self.body.px_x());
+        let vel_x = if is_player_right {
+            EGG_SPEED
+        } else {
+            -EGG_SPEED
+        };
+        let egg = Projectile::spiny_egg(self.body.px_x(), self.body.px_y() - 16, vel_x, EGG_THROW);
+        if ProjectileManager::spawn(egg).is_err() {
+            gba_warning!("Projectile pool full, dropped spiny egg");
+        }
+    }
+}
+
+impl EnemyImpl for Lakitu {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        if self.state == LakituState::KnockedOut {
+            self.body.apply_gravity();
+            return !self.body.step_free();
+        }
+
+        let player = PlayerManager::hitbox();
+        let screen = ScreenManager::get_screen_info();
+        self.follow(player, &screen);
+
+        self.throw_ticks = self.throw_ticks.saturating_sub(1);
+        if self.throw_ticks == 0 {
+            self.throw_egg(player);
+            self.throw_ticks = rng::jitter(THROW_TICKS, 1);
+        }
+        true
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            LakituState::Floating => Some(Hitbox {
+                x: self.body.px_x(),
+                y: self.body.px_y() - 13,
+                w: 16,
+                h: 27,
+            }),
+            LakituState::KnockedOut => None,
+        }
+    }
+
+    fn on_player_contact(&mut self, player: &Hitbox, player_falling: bool) -> ContactResult {
+        let Some(hitbox) = self.hitbox() else {
+            return ContactResult::Ignored;
+        };
+        if !is_stomp(player, player_falling, &hitbox) {
+            return ContactResult::HurtPlayer;
+        }
+
+        self.knock_out();
+        ContactResult::Stomped
+    }
+
+    fn knock_out(&mut self) {
+        self.state = LakituState::KnockedOut;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        let tile = if self.throw_ticks < WIND_UP_TICKS {
+            LakituAnimationTileIdx::Throwing
+        } else {
+            LakituAnimationTileIdx::Floating
+        };
+        // The sprite hangs 16px above the cloud
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y((y - 16).clamp(-32, 160) as u16);
+        self.otr.2 = self.otr.2.with_tile_id(tile.tile_id());
+        self.otr.1 = self.otr.1.with_vflip(self.state == LakituState::KnockedOut);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
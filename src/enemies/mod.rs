@@ -0,0 +1,290 @@
+use enum_dispatch::enum_dispatch;
+use gba::prelude::*;
+
+use crate::{
+    effects::points::ScoreAmount,
+    enemies::{
+        bowser::Bowser, bowser_fire::BowserFire, bullet_bill::BulletBill, cheep_cheep::CheepCheep,
+        goomba::Goomba, hammer_bro::HammerBro, koopa::Koopa, lakitu::Lakitu, piranha::Piranha,
+        spiny::Spiny,
+    },
+    entity::{EntityBody, Hitbox},
+    ewram_static,
+    fixed_bag::FixedBag,
+    gba_warning,
+    levels::shared::EnemyKind,
+    oam::OamManager,
+    player::PlayerManager,
+    scoring,
+    screen::{ScreenInfo, ScreenManager},
+    static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+pub mod bowser;
+pub mod bowser_fire;
+pub mod bullet_bill;
+pub mod cheep_cheep;
+pub mod goomba;
+pub mod hammer_bro;
+pub mod koopa;
+pub mod lakitu;
+pub mod piranha;
+pub mod spiny;
+
+// Enemies own a contiguous block of OAM right after the player
+pub const ENEMY_OAM_START: usize = 1;
+pub const MAX_ENEMIES: usize = 6;
+
+pub enum ContactResult {
+    Ignored,
+    /// Bounce the player and score the next step of their stomp chain
+    Stomped,
+    /// Bounce the player without touching the stomp chain, the enemy handled its own score
+    Bounced,
+    HurtPlayer,
+}
+
+#[enum_dispatch]
+pub trait EnemyImpl {
+    /// Runs AI and physics, returning false once the enemy should be removed
+    fn tick(&mut self, ctx: &TickContext) -> bool;
+    fn body(&self) -> &EntityBody;
+    /// None while the enemy can't interact with the player (e.g. squished)
+    fn hitbox(&self) -> Option<Hitbox>;
+    fn on_player_contact(&mut self, player: &Hitbox, player_falling: bool) -> ContactResult;
+    /// Flip upside down and fall off the screen, e.g. when hit by a shell
+    fn knock_out(&mut self);
+    /// Area that knocks out any other enemy it touches, like a sliding shell
+    fn attack_hitbox(&self) -> Option<Hitbox> {
+        None
+    }
+    /// Called when `attack_hitbox` knocked out another enemy, returns the score for that kill
+    fn on_attack_kill(&mut self) -> ScoreAmount {
+        ScoreAmount::OneHundred
+    }
+    /// False for enemies that shrug off shells, stars and bumped blocks
+    fn can_knock_out(&self) -> bool {
+        true
+    }
+    /// Hands over an enemy this one just created, like a projectile, for the manager to add
+    fn take_spawn(&mut self) -> Option<Enemy> {
+        None
+    }
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize);
+}
+
+#[enum_dispatch(EnemyImpl)]
+pub enum Enemy {
+    Goomba,
+    Koopa,
+    Piranha,
+    Bowser,
+    BowserFire,
+    HammerBro,
+    Lakitu,
+    Spiny,
+    BulletBill,
+    CheepCheep,
+}
+
+impl Enemy {
+    /// Builds the enemy for a level spawn item, row is in 8px units and col in 16px units
+    pub fn from_spawn(kind: EnemyKind, row: usize, col: usize) -> Self {
+        match kind {
+            EnemyKind::Goomba => Goomba::new(row, col).as_enemy(),
+            EnemyKind::Koopa => Koopa::new(row, col).as_enemy(),
+            EnemyKind::Bowser => Bowser::new(row, col).as_enemy(),
+            EnemyKind::HammerBro => HammerBro::new(row, col).as_enemy(),
+            EnemyKind::Lakitu => Lakitu::new(row, col).as_enemy(),
+        }
+    }
+}
+
+/// A player landing on the top half of an enemy counts as a stomp, anything else is a side hit
+pub fn is_stomp(player: &Hitbox, player_falling: bool, enemy: &Hitbox) -> bool {
+    player_falling && player.bottom() - enemy.y <= 8
+}
+
+/// Pops a knocked out body up and away, it then falls through the level with `EntityBody::step_free`
+pub fn launch_knocked_out(body: &mut EntityBody) {
+    body.vel_y = i32fx8::from_bits(-768);
+    body.vel_x = if body.is_moving_left() {
+        i32fx8::from_bits(-64)
+    } else {
+        i32fx8::from_bits(64)
+    };
+}
+
+pub struct EnemyManager {
+    enemies: FixedBag<Enemy, MAX_ENEMIES>,
+}
+
+impl EnemyManager {
+    pub const fn new() -> Self {
+        EnemyManager {
+            enemies: FixedBag::new(),
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.enemies.clear();
+        for idx in 0..MAX_ENEMIES {
+            OamManager::hide(ENEMY_OAM_START + idx);
+        }
+    }
+
+    pub fn on_start() {
+        Enemies.init();
+    }
+
+    pub fn spawn(enemy: Enemy) -> Result<usize, Enemy> {
+        Enemies.assume_init().enemies.push(enemy)
+    }
+
+    /// Number of live enemies matching the filter, used to cap what spawners put out
+    pub fn count(filter: impl Fn(&Enemy) -> bool) -> usize {
+        Enemies
+            .assume_init()
+            .enemies
+            .iter()
+            .filter(|(_idx, enemy)| filter(enemy))
+            .count()
+    }
+
+    /// Knocks out every enemy standing on the metatile at row (8px units) and col (16px units),
+    /// used when that block gets hit from below
+    pub fn bump_from_below(row: usize, col: usize) {
+        let manager = Enemies.assume_init();
+        let block_x = (col << 4) as i32;
+        for (_idx, enemy) in manager.enemies.iter_mut() {
+            let Some(hitbox) = enemy.hitbox() else {
+                continue;
+            };
+            let is_on_block = (hitbox.bottom() >> 3) as usize == row
+                && hitbox.x < block_x + 16
+                && block_x < hitbox.x + hitbox.w;
+            if !is_on_block || !enemy.can_knock_out() {
+                continue;
+            }
+
+            enemy.knock_out();
+            scoring::award_above(enemy.body(), ScoreAmount::OneHundred);
+        }
+    }
+
+    pub fn tick(tick: TickContext) {
+        let manager = Enemies.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        let mut player = PlayerManager::hitbox();
+        let player_falling = PlayerManager::is_falling();
+
+        let mut spawned: [Option<Enemy>; MAX_ENEMIES] = [const { None }; MAX_ENEMIES];
+
+        for (idx, slot) in manager.enemies.iter_mut_opt() {
+            let Some(enemy) = slot else {
+                continue;
+            };
+
+            if !enemy.tick(&tick) || enemy.body().is_offscreen(&screen) {
+                *slot = None;
+                continue;
+            }
+            spawned[idx] = enemy.take_spawn();
+
+            let Some(player_box) = player else {
+                continue;
+            };
+            let Some(enemy_box) = enemy.hitbox() else {
+                continue;
+            };
+            if !player_box.overlaps(&enemy_box) {
+                continue;
+            }
+
+            if PlayerManager::is_star() && enemy.can_knock_out() {
+                enemy.knock_out();
+                scoring::award_above(enemy.body(), ScoreAmount::OneHundred);
+                continue;
+            }
+
+            match enemy.on_player_contact(&player_box, player_falling) {
+                ContactResult::Ignored => {}
+                ContactResult::Stomped => {
+                    let amount = PlayerManager::stomp_bounce(tick.keys.a());
+                    scoring::award_above(enemy.body(), amount);
+                    // Only resolve one stomp per frame, the bounce moves the player off anything else
+                    player = None;
+                }
+                ContactResult::Bounced => {
+                    PlayerManager::bounce(tick.keys.a());
+                    player = None;
+                }
+                ContactResult::HurtPlayer => {
+                    PlayerManager::damage();
+                    player = None;
+                }
+            }
+        }
+
+        for enemy in spawned.into_iter().flatten() {
+            if manager.enemies.push(enemy).is_err() {
+                gba_warning!("Enemy pool full, dropped spawned enemy");
+            }
+        }
+
+        manager.resolve_attacks();
+    }
+
+    fn resolve_attacks(&mut self) {
+        for attacker_idx in 0..MAX_ENEMIES {
+            let Some(attack) = self
+                .enemies
+                .get(attacker_idx)
+                .and_then(|enemy| enemy.attack_hitbox())
+            else {
+                continue;
+            };
+
+            for target_idx in 0..MAX_ENEMIES {
+                if target_idx == attacker_idx {
+                    continue;
+                }
+                let Some(target) = self.enemies.get_mut(target_idx) else {
+                    continue;
+                };
+                let Some(target_box) = target.hitbox() else {
+                    continue;
+                };
+                if !attack.overlaps(&target_box) || !target.can_knock_out() {
+                    continue;
+                }
+
+                target.knock_out();
+                let target_body = *target.body();
+                if let Some(attacker) = self.enemies.get_mut(attacker_idx) {
+                    scoring::award_above(&target_body, attacker.on_attack_kill());
+                }
+            }
+        }
+    }
+
+    pub fn post_tick() {
+        let manager = Enemies.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        for idx in 0..MAX_ENEMIES {
+            match manager.enemies.get_mut(idx) {
+                Some(enemy) => enemy.post_tick(&screen, ENEMY_OAM_START + idx),
+                None => OamManager::hide(ENEMY_OAM_START + idx),
+            }
+        }
+    }
+}
+
+unsafe impl StaticInitSafe for EnemyManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Enemies: EnemyManager = EnemyManager::new());
@@ -0,0 +1,190 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::PIRANHA_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    player::PlayerManager,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PiranhaAnimationTileIdx {
+    Closed = 0,
+    Open = 1 * 8,
+}
+
+impl PiranhaAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (PIRANHA_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+// How far the plant sticks out of the pipe when fully up
+const EMERGED_HEIGHT: u8 = 24;
+const HIDDEN_TICKS: u8 = 90;
+const EMERGED_TICKS: u8 = 60;
+// Horizontal distance from the plant's center where the player keeps it in the pipe
+const SHY_DISTANCE: i32 = 28;
+// Sorts the plant behind BG2, which sits at priority 1 so the pipe tiles cover it
+const BEHIND_PIPE_PRIORITY: u16 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PiranhaState {
+    Hidden { ticks_left: u8 },
+    Rising,
+    Emerged { ticks_left: u8 },
+    Retracting,
+    Dead,
+}
+
+pub struct Piranha {
+    body: EntityBody,
+    // Top of the pipe in world pixels
+    pipe_top: i32,
+    emerged_by: u8,
+    state: PiranhaState,
+    anim_tick: u8,
+    otr: ObjAttr,
+}
+
+impl Piranha {
+    /// Lives in the pipe whose top is on the given row (8px units) and whose left half is at col (16px units)
+    pub fn new(pipe_row: usize, col: usize) -> Self {
+        let pipe_top = (pipe_row << 3) as i32;
+        let body = EntityBody::new(
+            i32fx8::wrapping_from((col << 4) as i32 + 8),
+            i32fx8::wrapping_from(pipe_top),
+        );
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Vertical)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(2);
+        otr.2 = otr
+            .2
+            .with_tile_id(PiranhaAnimationTileIdx::Closed.tile_id())
+            .with_priority(BEHIND_PIPE_PRIORITY)
+            .with_palbank(0);
+
+        Piranha {
+            body,
+            pipe_top,
+            emerged_by: 0,
+            state: PiranhaState::Hidden {
+                ticks_left: HIDDEN_TICKS,
+            },
+            anim_tick: 0,
+            otr,
+        }
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::Piranha(self)
+    }
+
+    fn is_player_close(&self) -> bool {
+        let Some(player) = PlayerManager::hitbox() else {
+            return false;
+        };
+        let player_center = player.x + (player.w >> 1);
+        let center = self.body.px_x() + 8;
+        (player_center - center).abs() < SHY_DISTANCE
+    }
+
+    fn set_emerged_by(&mut self, emerged_by: u8) {
+        self.emerged_by = emerged_by;
+        self.body.y = i32fx8::wrapping_from(self.pipe_top - emerged_by as i32);
+    }
+}
+
+impl EnemyImpl for Piranha {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        self.anim_tick = self.anim_tick.wrapping_add(1);
+
+        match self.state {
+            PiranhaState::Dead => return false,
+            PiranhaState::Hidden { ticks_left } => {
+                if ticks_left > 0 {
+                    self.state = PiranhaState::Hidden {
+                        ticks_left: ticks_left - 1,
+                    };
+                } else if !self.is_player_close() {
+                    self.state = PiranhaState::Rising;
+                }
+            }
+            PiranhaState::Rising => {
+                self.set_emerged_by(self.emerged_by + 1);
+                if self.emerged_by >= EMERGED_HEIGHT {
+                    self.state = PiranhaState::Emerged {
+                        ticks_left: EMERGED_TICKS,
+                    };
+                }
+            }
+            PiranhaState::Emerged { ticks_left } => {
+                self.state = if ticks_left == 0 {
+                    PiranhaState::Retracting
+                } else {
+                    PiranhaState::Emerged {
+                        ticks_left: ticks_left - 1,
+                    }
+                };
+            }
+            PiranhaState::Retracting => {
+                self.set_emerged_by(self.emerged_by - 1);
+                if self.emerged_by == 0 {
+                    self.state = PiranhaState::Hidden {
+                        ticks_left: HIDDEN_TICKS,
+                    };
+                }
+            }
+        }
+
+        true
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        if self.emerged_by == 0 || self.state == PiranhaState::Dead {
+            return None;
+        }
+        Some(Hitbox {
+            x: self.body.px_x() + 2,
+            y: self.body.px_y(),
+            w: 12,
+            h: self.emerged_by as i32,
+        })
+    }
+
+    fn on_player_contact(&mut self, _player: &Hitbox, _player_falling: bool) -> ContactResult {
+        // Can't be stomped, landing on it still hurts
+        ContactResult::HurtPlayer
+    }
+
+    fn knock_out(&mut self) {
+        self.state = PiranhaState::Dead;
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        let tile = if self.anim_tick & 0b1000 == 0 {
+            PiranhaAnimationTileIdx::Closed
+        } else {
+            PiranhaAnimationTileIdx::Open
+        };
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-32, 160) as u16);
+        self.otr.2 = self.otr.2.with_tile_id(tile.tile_id());
+        OamManager::write(oam_idx, self.otr);
+    }
+}
@@ -0,0 +1,135 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::SPINY_TILE_IDX_START,
+    enemies::{ContactResult, Enemy, EnemyImpl, launch_knocked_out},
+    entity::{EntityBody, Hitbox},
+    oam::OamManager,
+    screen::ScreenInfo,
+    tick::TickContext,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SpinyAnimationTileIdx {
+    Walking = 0,
+    // Only drawn by the projectile pool while the egg is in the air
+    Egg = 1 * 4,
+}
+
+impl SpinyAnimationTileIdx {
+    fn tile_id(self) -> u16 {
+        (SPINY_TILE_IDX_START as u16 + self as u16) * 2
+    }
+}
+
+const WALK_SPEED: i32fx8 = i32fx8::from_bits(128);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SpinyState {
+    Walking,
+    KnockedOut,
+}
+
+pub struct Spiny {
+    body: EntityBody,
+    state: SpinyState,
+    anim_tick: u8,
+    otr: ObjAttr,
+}
+
+impl Spiny {
+    /// Spiny hatched from a landed egg, walking towards the player if there is one
+    pub fn hatch(egg: EntityBody, player: Option<Hitbox>) -> Self {
+        let mut body = EntityBody::new(egg.x, egg.y);
+        let is_player_right = player.is_some_and(|player| player.x > body.px_x());
+        body.vel_x = if is_player_right {
+            WALK_SPEED
+        } else {
+            -WALK_SPEED
+        };
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(1);
+        otr.2 = otr
+            .2
+            .with_tile_id(SpinyAnimationTileIdx::Walking.tile_id())
+            .with_priority(0)
+            .with_palbank(0);
+
+        Spiny {
+            body,
+            state: SpinyState::Walking,
+            anim_tick: 0,
+            otr,
+        }
+    }
+
+    pub fn egg_tile_id() -> u16 {
+        SpinyAnimationTileIdx::Egg.tile_id()
+    }
+
+    pub fn as_enemy(self) -> Enemy {
+        Enemy::Spiny(self)
+    }
+}
+
+impl EnemyImpl for Spiny {
+    fn tick(&mut self, _ctx: &TickContext) -> bool {
+        self.body.apply_gravity();
+        if self.state == SpinyState::KnockedOut {
+            return !self.body.step_free();
+        }
+
+        let collision = self.body.step();
+        if collision.fell_out {
+            return false;
+        }
+        if collision.hit_wall {
+            self.body.vel_x = -self.body.vel_x;
+        }
+        self.anim_tick = self.anim_tick.wrapping_add(1);
+        true
+    }
+
+    fn body(&self) -> &EntityBody {
+        &self.body
+    }
+
+    fn hitbox(&self) -> Option<Hitbox> {
+        match self.state {
+            SpinyState::Walking => Some(self.body.hitbox()),
+            SpinyState::KnockedOut => None,
+        }
+    }
+
+    fn on_player_contact(&mut self, _player: &Hitbox, _player_falling: bool) -> ContactResult {
+        // The spikes make stomping hurt as much as walking into it
+        ContactResult::HurtPlayer
+    }
+
+    fn knock_out(&mut self) {
+        self.state = SpinyState::KnockedOut;
+        launch_knocked_out(&mut self.body);
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-16, 160) as u16);
+        // Same mirrored walk as the goomba
+        let flip = self.state == SpinyState::Walking && self.anim_tick & 0b1000 != 0;
+        self.otr.1 = self
+            .otr
+            .1
+            .with_hflip(flip)
+            .with_vflip(self.state == SpinyState::KnockedOut);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
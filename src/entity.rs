@@ -0,0 +1,161 @@
+use gba::prelude::*;
+
+use crate::{level_manager::LevelManager, screen::ScreenInfo};
+
+pub const GRAVITY: i32fx8 = i32fx8::from_bits(128);
+pub const MAX_FALL_SPEED: i32fx8 = i32fx8::from_bits(1600);
+
+/// Axis aligned box in world pixels, used for entity <-> entity overlap checks
+#[derive(Clone, Copy)]
+pub struct Hitbox {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl Hitbox {
+    pub fn overlaps(&self, other: &Hitbox) -> bool {
+        self.x < other.x + other.w
+            && other.x < self.x + self.w
+            && self.y < other.y + other.h
+            && other.y < self.y + self.h
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.h
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct CollisionResult {
+    pub hit_wall: bool,
+    pub on_ground: bool,
+    pub fell_out: bool,
+}
+
+/// Position and velocity of a 16x16 world object, in the same world pixel space as the player
+#[derive(Clone, Copy)]
+pub struct EntityBody {
+    pub x: i32fx8,
+    pub y: i32fx8,
+    pub vel_x: i32fx8,
+    pub vel_y: i32fx8,
+}
+
+impl EntityBody {
+    pub const fn new(x: i32fx8, y: i32fx8) -> Self {
+        EntityBody {
+            x,
+            y,
+            vel_x: i32fx8::wrapping_from(0),
+            vel_y: i32fx8::wrapping_from(0),
+        }
+    }
+
+    pub fn px_x(&self) -> i32 {
+        self.x.to_bits() >> 8
+    }
+
+    pub fn px_y(&self) -> i32 {
+        self.y.to_bits() >> 8
+    }
+
+    // 8 to remove fractional and 3 to convert to rows instead of raw pixels
+    pub fn row(&self) -> u16 {
+        (self.y.to_bits() >> (8 + 3)) as u16
+    }
+
+    pub fn col(&self) -> u16 {
+        (self.x.to_bits() >> (8 + 3)) as u16
+    }
+
+    pub fn hitbox(&self) -> Hitbox {
+        Hitbox {
+            x: self.px_x(),
+            y: self.px_y(),
+            w: 16,
+            h: 16,
+        }
+    }
+
+    pub fn is_moving_left(&self) -> bool {
+        self.vel_x < i32fx8::wrapping_from(0)
+    }
+
+    pub fn apply_gravity(&mut self) {
+        self.vel_y = self.vel_y.add(GRAVITY);
+        if self.vel_y > MAX_FALL_SPEED {
+            self.vel_y = MAX_FALL_SPEED;
+        }
+    }
+
+    /// Moves the body by its velocity against the level collision data.
+    /// Walls zero out horizontal movement for this frame and ground snaps the body to the top of the tile.
+    pub fn step(&mut self) -> CollisionResult {
+        let mut result = CollisionResult::default();
+
+        let next_x = self.x.add(self.vel_x);
+        let row = self.row();
+        if row >= 30 {
+            self.x = next_x;
+            self.y = self.y.add(self.vel_y);
+            result.fell_out = self.row() >= 32;
+            return result;
+        }
+
+        let mask_body = 0b11 << row;
+        let leading_col = if self.is_moving_left() {
+            (next_x.to_bits() >> (8 + 3)) as u16
+        } else {
+            ((next_x.to_bits() >> 8) + 15) as u16 >> 3
+        };
+        if self.vel_x != i32fx8::default()
+            && LevelManager::collision_mask(leading_col) & mask_body != 0
+        {
+            result.hit_wall = true;
+        } else {
+            self.x = next_x;
+        }
+
+        let next_y = self.y.add(self.vel_y);
+        let feet_row = (((next_y.to_bits() >> 8) + 16) >> 3) as u16;
+        let is_falling = self.vel_y >= i32fx8::default();
+        let left = LevelManager::collision_mask(self.col());
+        let right = LevelManager::collision_mask(((self.px_x() + 15) >> 3) as u16);
+        let mask_under = if feet_row < 32 { 0b1 << feet_row } else { 0 };
+
+        if is_falling && (left | right) & mask_under != 0 {
+            self.y = i32fx8::wrapping_from(((feet_row as i32 - 2) << 3) + 1);
+            self.vel_y = i32fx8::default();
+            result.on_ground = true;
+        } else {
+            self.y = next_y;
+        }
+
+        result.fell_out = self.row() >= 32;
+        result
+    }
+
+    /// Moves the body by its velocity ignoring the level, used for things falling off the screen.
+    /// Returns true once the body has dropped below the bottom of the level, bodies still rising
+    /// up from below it are kept
+    pub fn step_free(&mut self) -> bool {
+        self.x = self.x.add(self.vel_x);
+        self.y = self.y.add(self.vel_y);
+        self.vel_y >= i32fx8::default() && self.px_y() >= 32 * 8
+    }
+
+    /// Screenspace (x, y) of the top left of the body
+    pub fn to_screenspace(&self, screen: &ScreenInfo) -> (i32, i32) {
+        (
+            self.x.sub(screen.affn_x).to_bits() >> 8,
+            self.y.sub(screen.affn_y).to_bits() >> 8,
+        )
+    }
+
+    pub fn is_offscreen(&self, screen: &ScreenInfo) -> bool {
+        let (x, y) = self.to_screenspace(screen);
+        x < -32 || x > 240 + 64 || y > 160 + 32
+    }
+}
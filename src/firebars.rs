@@ -0,0 +1,223 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::FIREBAR_TILE_IDX_START,
+    entity::Hitbox,
+    ewram_static,
+    fixed_bag::FixedBag,
+    levels::shared::FirebarSpin,
+    math::{cos_u8, sin_u8},
+    oam::{OamHandle, OamManager},
+    player::PlayerManager,
+    screen::{ScreenInfo, ScreenManager},
+    static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+const MAX_FIREBARS: usize = 2;
+const MAX_LINKS: usize = 6;
+
+// Links are 8x8 fireballs spaced 8px apart starting on the pivot
+const LINK_SPACING: i32 = 8;
+// The fireballs are drawn 8x8 but only hurt in the middle
+const LINK_HITBOX_INSET: i32 = 1;
+
+impl FirebarSpin {
+    /// Angle change per tick, in 256ths of a turn with 8 fractional bits
+    fn speed(self) -> i16 {
+        match self {
+            FirebarSpin::Clockwise => 384,
+            FirebarSpin::CounterClockwise => -384,
+            FirebarSpin::FastClockwise => 768,
+            FirebarSpin::FastCounterClockwise => -768,
+        }
+    }
+}
+
+pub struct Firebar {
+    // Center of the block the bar turns around, in world pixels
+    pivot_x: i32,
+    pivot_y: i32,
+    // 256ths of a turn with 8 fractional bits, the top byte is what sin_u8/cos_u8 take
+    angle: u16,
+    speed: i16,
+    links: usize,
+    // A sprite per link, freed along with the bar
+    oam: [Option<OamHandle>; MAX_LINKS],
+    anim_tick: u8,
+}
+
+impl Firebar {
+    /// Firebar turning around the block on row (8px units) and col (16px units), starting pointed right
+    fn new(
+        row: usize,
+        col: usize,
+        links: usize,
+        spin: FirebarSpin,
+        oam: [Option<OamHandle>; MAX_LINKS],
+    ) -> Self {
+        Firebar {
+            pivot_x: ((col << 4) + 8) as i32,
+            pivot_y: ((row << 3) + 8) as i32,
+            angle: 0,
+            speed: spin.speed(),
+            links,
+            oam,
+            anim_tick: 0,
+        }
+    }
+
+    /// Top left of the given link in world pixels
+    fn link_pos(&self, link: usize) -> (i32, i32) {
+        let turn = (self.angle >> 8) as u8;
+        let distance = i32fx8::wrapping_from(link as i32 * LINK_SPACING);
+        let x = cos_u8(turn).mul(distance).to_bits() >> 8;
+        let y = sin_u8(turn).mul(distance).to_bits() >> 8;
+        (self.pivot_x + x - 4, self.pivot_y + y - 4)
+    }
+
+    fn link_hitbox(&self, link: usize) -> Hitbox {
+        let (x, y) = self.link_pos(link);
+        Hitbox {
+            x: x + LINK_HITBOX_INSET,
+            y: y + LINK_HITBOX_INSET,
+            w: 8 - LINK_HITBOX_INSET * 2,
+            h: 8 - LINK_HITBOX_INSET * 2,
+        }
+    }
+
+    fn tick(&mut self) {
+        self.angle = self.angle.wrapping_add_signed(self.speed);
+        self.anim_tick = self.anim_tick.wrapping_add(1);
+    }
+
+    fn is_offscreen(&self, screen: &ScreenInfo) -> bool {
+        let reach = self.links as i32 * LINK_SPACING;
+        let right_edge = self.pivot_x + reach;
+        right_edge < screen.affn_x.to_bits() >> 8
+    }
+
+    fn post_tick(&self, screen: &ScreenInfo) {
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr
+            .1
+            .with_size(0)
+            // Spin the flames by cycling through the flips
+            .with_hflip(self.anim_tick & 0b100 != 0)
+            .with_vflip(self.anim_tick & 0b1000 != 0);
+        otr.2 = otr
+            .2
+            .with_tile_id(FIREBAR_TILE_IDX_START as u16 * 2)
+            .with_priority(0)
+            .with_palbank(0);
+
+        let screen_x = screen.affn_x.to_bits() >> 8;
+        let screen_y = screen.affn_y.to_bits() >> 8;
+        for (link, oam) in self.oam.iter().enumerate() {
+            let Some(oam) = oam else {
+                continue;
+            };
+            let (x, y) = self.link_pos(link);
+            let (x, y) = (x - screen_x, y - screen_y);
+            if x < -8 || x >= 240 || y < -8 || y >= 160 {
+                oam.hide();
+                continue;
+            }
+            otr.set_x(x as u16);
+            otr.set_y(y as u16);
+            oam.write(otr);
+        }
+    }
+}
+
+pub struct FirebarManager {
+    firebars: FixedBag<Firebar, MAX_FIREBARS>,
+}
+
+impl FirebarManager {
+    pub const fn new() -> Self {
+        FirebarManager {
+            firebars: FixedBag::new(),
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.firebars.clear();
+    }
+
+    pub fn on_start() {
+        Firebars.init();
+    }
+
+    /// Adds a firebar around the block at row (8px units) and col (16px units)
+    pub fn spawn(
+        row: usize,
+        col: usize,
+        links: u8,
+        spin: FirebarSpin,
+    ) -> Result<usize, FirebarSpin> {
+        let links = (links as usize).min(MAX_LINKS);
+        let oam: [Option<OamHandle>; MAX_LINKS] = core::array::from_fn(|link| {
+            if link < links {
+                OamManager::alloc()
+            } else {
+                None
+            }
+        });
+        // Only place the bar if every link has a sprite, dropping the handles frees the rest
+        if oam[..links].iter().any(Option::is_none) {
+            return Err(spin);
+        }
+        Firebars
+            .assume_init()
+            .firebars
+            .push(Firebar::new(row, col, links, spin, oam))
+            .map_err(|_| spin)
+    }
+
+    pub fn tick(_tick: TickContext) {
+        let manager = Firebars.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        let player = PlayerManager::hitbox();
+
+        for idx in 0..MAX_FIREBARS {
+            let Some(firebar) = manager.firebars.get_mut(idx) else {
+                continue;
+            };
+            firebar.tick();
+
+            if firebar.is_offscreen(&screen) {
+                manager.firebars.take(idx);
+                continue;
+            }
+
+            if let Some(player) = player
+                && (0..firebar.links).any(|link| firebar.link_hitbox(link).overlaps(&player))
+            {
+                PlayerManager::damage();
+            }
+        }
+    }
+
+    pub fn post_tick() {
+        let manager = Firebars.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        for (_idx, firebar) in manager.firebars.iter() {
+            firebar.post_tick(&screen);
+        }
+    }
+}
+
+unsafe impl StaticInitSafe for FirebarManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Firebars: FirebarManager = FirebarManager::new());
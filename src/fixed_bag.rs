@@ -21,6 +21,10 @@ impl<T, const N: usize> FixedBag<T, N> {
         Err(item)
     }
 
+    pub fn is_full(&self) -> bool {
+        self.items.iter().all(Option::is_some)
+    }
+
     pub fn remove(&mut self, index: usize) {
         if index < N {
             self.items[index] = None;
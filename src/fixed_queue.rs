@@ -26,6 +26,15 @@ impl<T, const N: usize> FixedQueue<T, N> {
         item
     }
 
+    /// Pushes to the back, handing the item back if the queue is full
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.len() == N {
+            return Err(item);
+        }
+        self.push_pop(item);
+        Ok(())
+    }
+
     pub fn push_pop(&mut self, item: T) -> Option<T> {
         let mut to_ret = None;
         if self.start_idx == self.next_idx {
@@ -51,6 +60,17 @@ impl<T, const N: usize> FixedQueue<T, N> {
         self.items[idx_to_get].as_ref()
     }
 
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        if idx >= N {
+            panic!("Index out of bounds on FixedQueue");
+        }
+        let mut idx_to_get = self.start_idx + idx;
+        if idx_to_get >= N {
+            idx_to_get -= N;
+        }
+        self.items[idx_to_get].as_mut()
+    }
+
     pub fn clear(&mut self) {
         for slot in &mut self.items {
             *slot = None;
@@ -0,0 +1,235 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::ITEM_TILE_IDX_START,
+    effects::points::ScoreAmount,
+    enemies::{ENEMY_OAM_START, MAX_ENEMIES},
+    entity::EntityBody,
+    ewram_static,
+    fixed_bag::FixedBag,
+    gba_warning,
+    levels::shared::ItemKind,
+    oam::OamManager,
+    player::PlayerManager,
+    scoring,
+    screen::{ScreenInfo, ScreenManager},
+    static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+// Items get the OAM block right after the enemies
+pub const ITEM_OAM_START: usize = ENEMY_OAM_START + MAX_ENEMIES;
+pub const MAX_ITEMS: usize = 2;
+
+const EMERGE_TICKS: u8 = 32;
+const WALK_SPEED: i32fx8 = i32fx8::from_bits(1 << 8);
+const STAR_BOUNCE: i32fx8 = i32fx8::from_bits(-1024);
+// While emerging the item sorts behind BG2 so it looks like it comes out of the block
+const BEHIND_BLOCK_PRIORITY: u16 = 2;
+
+impl ItemKind {
+    fn tile_id(self) -> u16 {
+        let offset = match self {
+            ItemKind::Mushroom => 0,
+            ItemKind::OneUp => 1 * 4,
+            ItemKind::Starman => 2 * 4,
+        };
+        (ITEM_TILE_IDX_START as u16 + offset) * 2
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ItemState {
+    Emerging { ticks: u8 },
+    Moving,
+}
+
+pub struct Item {
+    kind: ItemKind,
+    body: EntityBody,
+    // Top of the block the item came out of, in world pixels
+    block_top: i32,
+    state: ItemState,
+    otr: ObjAttr,
+}
+
+impl Item {
+    /// Item coming out of the block at row (8px units) and col (16px units)
+    pub fn from_block(kind: ItemKind, row: usize, col: usize) -> Self {
+        let block_top = (row << 3) as i32;
+        let body = EntityBody::new(
+            i32fx8::wrapping_from((col << 4) as i32),
+            i32fx8::wrapping_from(block_top),
+        );
+
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(1);
+        otr.2 = otr
+            .2
+            .with_tile_id(kind.tile_id())
+            .with_priority(BEHIND_BLOCK_PRIORITY)
+            .with_palbank(0);
+
+        Item {
+            kind,
+            body,
+            block_top,
+            state: ItemState::Emerging { ticks: 0 },
+            otr,
+        }
+    }
+
+    /// Returns false once the item should be removed
+    fn tick(&mut self) -> bool {
+        match self.state {
+            ItemState::Emerging { ticks } => {
+                // Rise 16px over EMERGE_TICKS frames
+                let risen = ((ticks as i32) << 4) / EMERGE_TICKS as i32;
+                self.body.y = i32fx8::wrapping_from(self.block_top - risen);
+                if ticks + 1 >= EMERGE_TICKS {
+                    self.body.y = i32fx8::wrapping_from(self.block_top - 16 + 1);
+                    self.body.vel_x = WALK_SPEED;
+                    self.state = ItemState::Moving;
+                    self.otr.2 = self.otr.2.with_priority(0);
+                } else {
+                    self.state = ItemState::Emerging { ticks: ticks + 1 };
+                }
+                true
+            }
+            ItemState::Moving => {
+                self.body.apply_gravity();
+                let collision = self.body.step();
+                if collision.hit_wall {
+                    self.body.vel_x = -self.body.vel_x;
+                }
+                if collision.on_ground && self.kind == ItemKind::Starman {
+                    self.body.vel_y = STAR_BOUNCE;
+                }
+                !collision.fell_out
+            }
+        }
+    }
+
+    fn grant(&self) {
+        match self.kind {
+            ItemKind::Mushroom => {
+                PlayerManager::grow();
+                scoring::award_above(&self.body, ScoreAmount::OneThousand);
+            }
+            ItemKind::OneUp => scoring::award_above(&self.body, ScoreAmount::OneUp),
+            ItemKind::Starman => {
+                PlayerManager::grant_star();
+                scoring::award_above(&self.body, ScoreAmount::OneThousand);
+            }
+        }
+    }
+
+    fn post_tick(&mut self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        self.otr.set_x(x.clamp(-60, 240) as u16);
+        self.otr.set_y(y.clamp(-16, 160) as u16);
+        OamManager::write(oam_idx, self.otr);
+    }
+}
+
+pub struct ItemManager {
+    items: FixedBag<Item, MAX_ITEMS>,
+}
+
+impl ItemManager {
+    pub const fn new() -> Self {
+        ItemManager {
+            items: FixedBag::new(),
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.items.clear();
+        for idx in 0..MAX_ITEMS {
+            OamManager::hide(ITEM_OAM_START + idx);
+        }
+    }
+
+    pub fn on_start() {
+        Items.init();
+    }
+
+    pub fn spawn_from_block(kind: ItemKind, row: usize, col: usize) {
+        let manager = Items.assume_init();
+        if manager
+            .items
+            .push(Item::from_block(kind, row, col))
+            .is_err()
+        {
+            gba_warning!("Item pool full, dropped item from block");
+        }
+    }
+
+    /// Walking items on top of a block hit from below hop up and turn around
+    pub fn bump_from_below(row: usize, col: usize) {
+        let manager = Items.assume_init();
+        let block_x = (col << 4) as i32;
+        for (_idx, item) in manager.items.iter_mut() {
+            if item.state != ItemState::Moving {
+                continue;
+            }
+            let hitbox = item.body.hitbox();
+            let is_on_block = (hitbox.bottom() >> 3) as usize == row
+                && hitbox.x < block_x + 16
+                && block_x < hitbox.x + hitbox.w;
+            if is_on_block {
+                item.body.vel_x = -item.body.vel_x;
+                item.body.vel_y = i32fx8::from_bits(-768);
+            }
+        }
+    }
+
+    pub fn tick(_tick: TickContext) {
+        let manager = Items.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        let player = PlayerManager::hitbox();
+
+        for (_idx, slot) in manager.items.iter_mut_opt() {
+            let Some(item) = slot else {
+                continue;
+            };
+
+            if !item.tick() || item.body.is_offscreen(&screen) {
+                *slot = None;
+                continue;
+            }
+
+            if let Some(player) = player
+                && player.overlaps(&item.body.hitbox())
+            {
+                item.grant();
+                *slot = None;
+            }
+        }
+    }
+
+    pub fn post_tick() {
+        let manager = Items.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        for idx in 0..MAX_ITEMS {
+            match manager.items.get_mut(idx) {
+                Some(item) => item.post_tick(&screen, ITEM_OAM_START + idx),
+                None => OamManager::hide(ITEM_OAM_START + idx),
+            }
+        }
+    }
+}
+
+unsafe impl StaticInitSafe for ItemManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Items: ItemManager = ItemManager::new());
@@ -3,17 +3,22 @@ use core::ops::Add;
 use gba::prelude::*;
 
 use crate::{
+    enemies::{Enemy, EnemyManager, piranha::Piranha},
     ewram_static,
+    firebars::FirebarManager,
     fixed_bag::FixedBag,
     fixed_queue::FixedQueue,
     gba_warning,
     levels::shared::{
-        BUSH_LEFT, BUSH_MIDDLE, BUSH_RIGHT, LEVEL_1_1, Level, LevelFloor, LevelItem,
-        MOUNTAIL_BUTTONS, MOUNTAIL_EMPTY, MOUNTAIL_SLOPE_DOWN, MOUNTAIL_SLOPE_UP, MOUNTAIL_TOP,
-        PIPE_BODY_LEFT, PIPE_BODY_RIGHT, PIPE_TOP_LEFT, PIPE_TOP_RIGHT, Tile,
+        AXE, BLASTER_BASE, BLASTER_TOP, BRIDGE, BUSH_LEFT, BUSH_MIDDLE, BUSH_RIGHT, COIN, ItemKind,
+        LEVEL_1_1, Level, LevelFloor, LevelItem, MOUNTAIL_BUTTONS, MOUNTAIL_EMPTY,
+        MOUNTAIL_SLOPE_DOWN, MOUNTAIL_SLOPE_UP, MOUNTAIL_TOP, PIPE_BODY_LEFT, PIPE_BODY_RIGHT,
+        PIPE_TOP_LEFT, PIPE_TOP_RIGHT, PipeOccupant, QUESTION_BLOCK_USED, SpawnerKind, Tile,
     },
+    lifts::LiftManager,
     math::{Powers, mod_mask_u32},
     screen::ScreenManager,
+    spawners::SpawnerManager,
     static_init::StaticInitSafe,
     tick::TickContext,
 };
@@ -29,8 +34,21 @@ pub struct LevelManager {
     queue_start: usize,
     top_row: [u8; 32],
     background_col: usize,
+    // One bit per index into the level data, set once a spawn item has handed off its enemy
+    consumed_spawns: [u32; MAX_LEVEL_ITEMS / 32],
+    block_items: FixedBag<BlockItem, 8>,
 }
 
+/// Contents of a block that is currently streamed in, row in 8px units and col in 16px units
+#[derive(Clone, Copy)]
+struct BlockItem {
+    row: usize,
+    col: usize,
+    item: ItemKind,
+}
+
+const MAX_LEVEL_ITEMS: usize = 512;
+
 pub fn draw_tile(row: usize, col: usize, tile: Tile) {
     AFFINE2_SCREENBLOCKS
         .get_frame(16)
@@ -110,10 +128,52 @@ impl LevelManager {
             queue_start: 0,
             top_row: [35; 32],
             background_col: 0,
+            consumed_spawns: [0; MAX_LEVEL_ITEMS / 32],
+            block_items: FixedBag::new(),
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.consumed_spawns = [0; MAX_LEVEL_ITEMS / 32];
+        self.block_items.clear();
+        ScreenManager::set_level_bounds(
+            self.current_level.width_px(),
+            self.current_level.height_px(),
+        );
+    }
+
+    /// Removes and returns the item hidden in the block at row (8px units) and col (16px units)
+    pub fn take_block_item(row: usize, col: usize) -> Option<ItemKind> {
+        let manager = Level.assume_init();
+        for (_idx, slot) in manager.block_items.iter_mut_opt() {
+            if let Some(block) = slot
+                && block.row == row
+                && block.col == col
+            {
+                return slot.take().map(|block| block.item);
+            }
         }
+        None
+    }
+
+    fn is_spawn_consumed(&self, level_idx: usize) -> bool {
+        self.consumed_spawns[level_idx >> 5] & (1 << (level_idx & 31)) != 0
     }
 
-    fn reset_internal(&mut self) {}
+    fn consume_spawn(&mut self, level_idx: usize) {
+        self.consumed_spawns[level_idx >> 5] |= 1 << (level_idx & 31);
+    }
+
+    /// Runs the spawn for a level item, unless that item already spawned its entity
+    fn spawn_once<T>(&mut self, level_idx: usize, spawn: impl FnOnce() -> Result<usize, T>) {
+        if self.is_spawn_consumed(level_idx) {
+            return;
+        }
+        match spawn() {
+            Ok(_) => self.consume_spawn(level_idx),
+            Err(_) => gba_warning!("Pool full, dropped spawn {}", level_idx),
+        }
+    }
 
     pub fn on_start() {
         Level.init();
@@ -141,6 +201,26 @@ impl LevelManager {
         return 0;
     }
 
+    /// Removes a solid tile at row (8px units) and col (16px units) from both the screen and the collision
+    pub fn clear_solid(row: usize, col: usize) {
+        let manager = Level.assume_init();
+        let screenblock_col = mod_mask_u32(col as u32, Powers::_32) as usize;
+        clear_tile(row, screenblock_col);
+
+        // Each 16px column is stored twice in the stand matrix, once per 8px column
+        let Some(first) = (col << 1).checked_sub(manager.queue_start) else {
+            return;
+        };
+        for col_idx in first..first + 2 {
+            if col_idx >= manager.stand_matrix.len() {
+                return;
+            }
+            if let Some(mask) = manager.stand_matrix.get_mut(col_idx) {
+                *mask &= !(0b11 << row);
+            }
+        }
+    }
+
     fn process_screen(&mut self) {
         let screen_details = ScreenManager::get_screen_info();
         let start = screen_details.onscreen_col_start;
@@ -264,14 +344,59 @@ impl LevelManager {
             };
 
             while self.col_ptr <= (i as usize) && self.level_ptr < self.current_level.data.len() {
-                let item: LevelItem = self.current_level.data[self.level_ptr];
+                let item_idx = self.level_ptr;
+                let item: LevelItem = self.current_level.data[item_idx];
                 self.level_ptr += 1;
                 match item {
                     LevelItem::NextCol { advance_by } => {
                         self.col_ptr += advance_by;
                     }
+                    LevelItem::Spawn { kind, row } => {
+                        self.spawn_once(item_idx, || {
+                            EnemyManager::spawn(Enemy::from_spawn(kind, row << 1, i as usize))
+                        });
+                    }
+                    LevelItem::Firebar { row, links, spin } => {
+                        self.spawn_once(item_idx, || {
+                            FirebarManager::spawn(row << 1, i as usize, links, spin)
+                        });
+                        let _ = self.stack_of_renders.push(ManagedItem {
+                            item,
+                            col_start: i as usize,
+                        });
+                    }
+                    LevelItem::Lift { kind, row } => {
+                        self.spawn_once(item_idx, || {
+                            LiftManager::spawn(kind, row << 1, i as usize)
+                        });
+                    }
+                    LevelItem::Spawner { kind, row } => {
+                        self.spawn_once(item_idx, || {
+                            SpawnerManager::add(kind, row << 1, i as usize)
+                        });
+                        if kind == SpawnerKind::BulletBlaster {
+                            let _ = self.stack_of_renders.push(ManagedItem {
+                                item,
+                                col_start: i as usize,
+                            });
+                        }
+                    }
+                    LevelItem::Pipe { row, occupant } => {
+                        if occupant == PipeOccupant::Piranha {
+                            self.spawn_once(item_idx, || {
+                                EnemyManager::spawn(Piranha::new(row << 1, i as usize).as_enemy())
+                            });
+                        }
+                        let _ = self.stack_of_renders.push(ManagedItem {
+                            item,
+                            col_start: i as usize,
+                        });
+                    }
                     LevelItem::Tile { .. }
-                    | LevelItem::Pipe { .. }
+                    | LevelItem::ItemBlock { .. }
+                    | LevelItem::Coin { .. }
+                    | LevelItem::Bridge { .. }
+                    | LevelItem::Axe { .. }
                     | LevelItem::HoleInFloor { .. } => {
                         let _ = self.stack_of_renders.push(ManagedItem {
                             item,
@@ -286,12 +411,14 @@ impl LevelManager {
                     continue;
                 };
                 match inner.item {
-                    LevelItem::NextCol { .. } => {
+                    LevelItem::NextCol { .. }
+                    | LevelItem::Spawn { .. }
+                    | LevelItem::Lift { .. } => {
                         // This shouldn't happen but just in case
                         *managed = None;
                         continue;
                     }
-                    LevelItem::Pipe { row } => {
+                    LevelItem::Pipe { row, .. } => {
                         let row = row << 1;
                         top_drawn_row = top_drawn_row.min(row);
                         standable_mask |= 0b11 << row;
@@ -327,6 +454,72 @@ impl LevelManager {
                             *managed = None;
                         }
                     }
+                    LevelItem::Coin { row, len } => {
+                        // Coins are drawn but never solid, the player picks them up by overlap
+                        let row = row << 1;
+                        let col_in_item = i as usize - inner.col_start;
+                        if col_in_item < len {
+                            top_drawn_row = top_drawn_row.min(row);
+                            draw_tile(row, screenblock_col, COIN);
+                        } else {
+                            *managed = None;
+                        }
+                    }
+                    LevelItem::Spawner { row, .. } => {
+                        // Only blasters get rendered, a cannon on top of a base down to the floor
+                        let row = row << 1;
+                        top_drawn_row = top_drawn_row.min(row);
+                        standable_mask |= 0b11 << row;
+                        draw_tile(row, screenblock_col, BLASTER_TOP);
+                        let diff = floor_bottom_for_col.saturating_sub(row + 2) >> 1;
+                        for vert_row in 0..diff {
+                            let row = (row + 2) + vert_row * 2;
+                            standable_mask |= 0b11 << row;
+                            draw_tile(row, screenblock_col, BLASTER_BASE);
+                        }
+                        *managed = None;
+                    }
+                    LevelItem::Bridge { row, len } => {
+                        let row = row << 1;
+                        let col_in_item = i as usize - inner.col_start;
+                        if col_in_item < len {
+                            top_drawn_row = top_drawn_row.min(row);
+                            standable_mask |= 0b11 << row;
+                            draw_tile(row, screenblock_col, BRIDGE);
+                        } else {
+                            *managed = None;
+                        }
+                    }
+                    LevelItem::Axe { row } => {
+                        // Like coins the axe is touched, not stood on
+                        let row = row << 1;
+                        top_drawn_row = top_drawn_row.min(row);
+                        draw_tile(row, screenblock_col, AXE);
+                        *managed = None;
+                    }
+                    LevelItem::Firebar { row, .. } => {
+                        // Firebars turn around a solid used block
+                        let row = row << 1;
+                        top_drawn_row = top_drawn_row.min(row);
+                        standable_mask |= 0b11 << row;
+                        draw_tile(row, screenblock_col, QUESTION_BLOCK_USED);
+                        *managed = None;
+                    }
+                    LevelItem::ItemBlock { tile, row, item } => {
+                        let row = row << 1;
+                        top_drawn_row = top_drawn_row.min(row);
+                        standable_mask |= 0b11 << row;
+                        draw_tile(row, screenblock_col, tile);
+                        let block = BlockItem {
+                            row,
+                            col: inner.col_start,
+                            item,
+                        };
+                        if self.block_items.push(block).is_err() {
+                            gba_warning!("Too many item blocks streamed in, dropped one");
+                        }
+                        *managed = None;
+                    }
                     LevelItem::HoleInFloor { len } => {
                         let col_in_item = i as usize - inner.col_start;
 
@@ -380,6 +573,11 @@ impl LevelManager {
                 .write(u8x2::default().with_high(0).with_low(0));
         }
         self.top_row[screenblock_col] = 35;
+        for (_idx, slot) in self.block_items.iter_mut_opt() {
+            if slot.is_some_and(|block| block.col == i as usize) {
+                *slot = None;
+            }
+        }
         self.reaped_col += 2;
     }
 
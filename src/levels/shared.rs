@@ -40,6 +40,12 @@ pub const BRICK: Tile = Tile::new(get_tile_idx(0, 0));
 pub const QUESTION_BLOCK_UNUSED: Tile = Tile::new(get_tile_idx(0, 2));
 pub const QUESTION_BLOCK_USED: Tile = Tile::new(get_tile_idx(0, 3));
 pub const ROCK: Tile = Tile::new(get_tile_idx(0, 1));
+pub const COIN: Tile = Tile::new(get_tile_idx(1, 1));
+pub const SPRINGBOARD: Tile = Tile::new(get_tile_idx(2, 0));
+pub const BRIDGE: Tile = Tile::new(get_tile_idx(2, 1));
+pub const AXE: Tile = Tile::new(get_tile_idx(2, 2));
+pub const BLASTER_TOP: Tile = Tile::new(get_tile_idx(1, 4));
+pub const BLASTER_BASE: Tile = Tile::new(get_tile_idx(1, 5));
 pub const PIPE_TOP_LEFT: Tile = Tile::new(get_tile_idx(0, 6));
 pub const PIPE_TOP_RIGHT: Tile = Tile::new(get_tile_idx(0, 7));
 pub const PIPE_BODY_LEFT: Tile = Tile::new(get_tile_idx(1, 6));
@@ -60,12 +66,139 @@ pub struct Level {
     pub data: &'static [LevelItem],
 }
 
+// Levels are always 32 rows of 8px
+const LEVEL_HEIGHT_PX: i32 = 32 * 8;
+
+impl Level {
+    /// Width in pixels, up to the end of the rightmost item
+    pub fn width_px(&self) -> i32 {
+        let mut col = 0;
+        let mut end_col = 0;
+        for item in self.data {
+            let len = match *item {
+                LevelItem::NextCol { advance_by } => {
+                    col += advance_by;
+                    continue;
+                }
+                LevelItem::Tile { len, .. }
+                | LevelItem::HoleInFloor { len }
+                | LevelItem::Coin { len, .. }
+                | LevelItem::Bridge { len, .. } => len,
+                LevelItem::Pipe { .. } => 2,
+                _ => 1,
+            };
+            end_col = end_col.max(col + len);
+        }
+        (end_col << 4) as i32
+    }
+
+    pub fn height_px(&self) -> i32 {
+        LEVEL_HEIGHT_PX
+    }
+}
+
+/// Enemy for a `LevelItem::Spawn`, standing on top of the item's row and spawned once when its column streams in
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EnemyKind {
+    Goomba,
+    Koopa,
+    Bowser,
+    HammerBro,
+    Lakitu,
+}
+
+/// Contents of a `LevelItem::ItemBlock`, released when the block is hit from below
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Mushroom,
+    OneUp,
+    Starman,
+}
+
+/// Movement of a `LevelItem::Lift`, ranges and offsets are in 16px columns
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LiftKind {
+    Horizontal { range: u8 },
+    Vertical { range: u8 },
+    Falling,
+    Balance { partner_offset: u8 },
+}
+
+/// What a `LevelItem::Spawner` keeps emitting while the camera is near it, lengths are in 16px columns
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SpawnerKind {
+    /// Cannon standing on the item's row that fires Bullet Bills at the player
+    BulletBlaster,
+    /// Cheep-cheeps leap up from below the screen while the camera is over these columns
+    CheepCheeps { len: u8 },
+}
+
+/// Rotation of a `LevelItem::Firebar` around its block
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FirebarSpin {
+    Clockwise,
+    CounterClockwise,
+    FastClockwise,
+    FastCounterClockwise,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PipeOccupant {
+    Empty,
+    Piranha,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum LevelItem {
-    Tile { tile: Tile, row: usize, len: usize },
-    HoleInFloor { len: usize },
-    Pipe { row: usize },
-    NextCol { advance_by: usize },
+    Tile {
+        tile: Tile,
+        row: usize,
+        len: usize,
+    },
+    HoleInFloor {
+        len: usize,
+    },
+    Pipe {
+        row: usize,
+        occupant: PipeOccupant,
+    },
+    ItemBlock {
+        tile: Tile,
+        row: usize,
+        item: ItemKind,
+    },
+    Coin {
+        row: usize,
+        len: usize,
+    },
+    Spawn {
+        kind: EnemyKind,
+        row: usize,
+    },
+    Lift {
+        kind: LiftKind,
+        row: usize,
+    },
+    Firebar {
+        row: usize,
+        links: u8,
+        spin: FirebarSpin,
+    },
+    Spawner {
+        kind: SpawnerKind,
+        row: usize,
+    },
+    Bridge {
+        row: usize,
+        len: usize,
+    },
+    // Grabbing the axe drops the bridge that ends just left of it on the row below
+    Axe {
+        row: usize,
+    },
+    NextCol {
+        advance_by: usize,
+    },
 }
 
 const FLOOR: usize = 15;
@@ -86,8 +219,19 @@ pub const LEVEL_1_1_DATA: &[LevelItem] = &[
         row: from_floor(3),
         len: 4,
     },
+    LevelItem::NextCol { advance_by: 5 },
+    LevelItem::Coin {
+        row: from_floor(4),
+        len: 3,
+    },
+    LevelItem::NextCol { advance_by: 4 },
+    LevelItem::Tile {
+        tile: SPRINGBOARD,
+        row: from_floor(0),
+        len: 1,
+    },
     LevelItem::NextCol {
-        advance_by: SCREEN_WIDTH - 4,
+        advance_by: SCREEN_WIDTH - 4 - 5 - 4,
     },
     LevelItem::Tile {
         tile: QUESTION_BLOCK_UNUSED,
@@ -102,16 +246,20 @@ pub const LEVEL_1_1_DATA: &[LevelItem] = &[
         len: 5,
     },
     LevelItem::NextCol { advance_by: 1 },
-    LevelItem::Tile {
+    LevelItem::ItemBlock {
         tile: QUESTION_BLOCK_UNUSED,
         row: from_floor(3),
-        len: 1,
+        item: ItemKind::Mushroom,
     },
     LevelItem::NextCol { advance_by: 1 },
-    LevelItem::Tile {
+    LevelItem::ItemBlock {
         tile: BRICK,
         row: from_floor(7),
-        len: 1,
+        item: ItemKind::OneUp,
+    },
+    LevelItem::Spawn {
+        kind: EnemyKind::Goomba,
+        row: from_floor(0),
     },
     LevelItem::NextCol { advance_by: 1 },
     LevelItem::Tile {
@@ -120,15 +268,91 @@ pub const LEVEL_1_1_DATA: &[LevelItem] = &[
         len: 1,
     },
     LevelItem::NextCol { advance_by: 5 },
-    LevelItem::Pipe { row: from_floor(1) },
+    LevelItem::Pipe {
+        row: from_floor(1),
+        occupant: PipeOccupant::Empty,
+    },
     LevelItem::NextCol { advance_by: 10 },
-    LevelItem::Pipe { row: from_floor(2) },
-    LevelItem::NextCol { advance_by: 8 },
-    LevelItem::Pipe { row: from_floor(3) },
-    LevelItem::NextCol { advance_by: 11 },
-    LevelItem::Pipe { row: from_floor(3) },
-    LevelItem::NextCol { advance_by: 12 },
+    LevelItem::Pipe {
+        row: from_floor(2),
+        occupant: PipeOccupant::Empty,
+    },
+    LevelItem::NextCol { advance_by: 4 },
+    LevelItem::Spawn {
+        kind: EnemyKind::Goomba,
+        row: from_floor(0),
+    },
+    LevelItem::NextCol { advance_by: 4 },
+    LevelItem::Pipe {
+        row: from_floor(3),
+        occupant: PipeOccupant::Empty,
+    },
+    LevelItem::NextCol { advance_by: 5 },
+    LevelItem::Spawn {
+        kind: EnemyKind::Goomba,
+        row: from_floor(0),
+    },
+    LevelItem::NextCol { advance_by: 2 },
+    LevelItem::Spawn {
+        kind: EnemyKind::Goomba,
+        row: from_floor(0),
+    },
+    LevelItem::NextCol { advance_by: 4 },
+    LevelItem::Pipe {
+        row: from_floor(3),
+        occupant: PipeOccupant::Piranha,
+    },
+    LevelItem::NextCol { advance_by: 6 },
+    LevelItem::Spawn {
+        kind: EnemyKind::Koopa,
+        row: from_floor(0),
+    },
+    LevelItem::NextCol { advance_by: 6 },
     LevelItem::HoleInFloor { len: 2 },
+    LevelItem::Lift {
+        kind: LiftKind::Horizontal { range: 2 },
+        row: from_floor(1),
+    },
+    LevelItem::NextCol { advance_by: 6 },
+    LevelItem::Spawn {
+        kind: EnemyKind::Lakitu,
+        row: from_floor(9),
+    },
+    LevelItem::NextCol { advance_by: 4 },
+    LevelItem::Tile {
+        tile: BRICK,
+        row: from_floor(3),
+        len: 5,
+    },
+    LevelItem::Tile {
+        tile: BRICK,
+        row: from_floor(7),
+        len: 5,
+    },
+    LevelItem::NextCol { advance_by: 2 },
+    LevelItem::Spawn {
+        kind: EnemyKind::HammerBro,
+        row: from_floor(0),
+    },
+    LevelItem::NextCol { advance_by: 8 },
+    LevelItem::Spawner {
+        kind: SpawnerKind::BulletBlaster,
+        row: from_floor(1),
+    },
+    LevelItem::NextCol { advance_by: 4 },
+    LevelItem::Spawner {
+        kind: SpawnerKind::CheepCheeps { len: 8 },
+        row: FLOOR,
+    },
+    LevelItem::HoleInFloor { len: 8 },
+    LevelItem::Bridge { row: FLOOR, len: 8 },
+    LevelItem::NextCol { advance_by: 5 },
+    LevelItem::Spawn {
+        kind: EnemyKind::Bowser,
+        row: from_floor(0),
+    },
+    LevelItem::NextCol { advance_by: 3 },
+    LevelItem::Axe { row: from_floor(0) },
 ];
 
 pub const LEVEL_1_1: Level = Level {
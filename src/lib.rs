@@ -3,21 +3,32 @@
 
 pub mod allocator;
 pub mod assets;
+pub mod castle;
 pub mod color;
 pub mod effects;
+pub mod enemies;
+pub mod entity;
+pub mod firebars;
 pub mod fixed_bag;
 pub mod fixed_queue;
 pub mod fixed_string;
 pub mod fmt;
+pub mod items;
 pub mod keys;
 pub mod level_manager;
 pub mod levels;
+pub mod lifts;
 pub mod logger;
 pub mod math;
+pub mod oam;
 pub mod player;
+pub mod projectiles;
 pub mod rng;
+pub mod scoring;
 pub mod screen;
 pub mod screen_text;
+pub mod spawners;
 pub mod static_init;
 pub mod tick;
 pub mod topbar;
+pub mod transition;
@@ -0,0 +1,327 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::LIFT_TILE_IDX_START,
+    entity::{EntityBody, Hitbox},
+    ewram_static,
+    fixed_bag::FixedBag,
+    items::{ITEM_OAM_START, MAX_ITEMS},
+    levels::shared::LiftKind,
+    oam::OamManager,
+    screen::{ScreenInfo, ScreenManager},
+    static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+// Each lift is drawn as LIFT_SEGMENTS 16x8 sprites side by side, all from the OAM block after the items
+pub const LIFT_OAM_START: usize = ITEM_OAM_START + MAX_ITEMS;
+pub const MAX_LIFTS: usize = 4;
+pub const LIFT_SEGMENTS: usize = 3;
+pub const LIFT_WIDTH: i32 = 16 * LIFT_SEGMENTS as i32;
+
+const LIFT_SPEED: i32fx8 = i32fx8::from_bits(1 << 8);
+// Falling lifts only drop while someone is standing on them
+const FALL_SPEED: i32fx8 = i32fx8::from_bits(2 << 8);
+const BALANCE_SPEED: i32fx8 = i32fx8::from_bits(1 << 8);
+// How far either side of a balance lift can be pulled up before the rope snaps and both drop
+const BALANCE_TRAVEL: i32 = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LiftState {
+    Moving,
+    Dropping,
+}
+
+pub struct Lift {
+    kind: LiftKind,
+    // Top left of the lift, the top edge is the solid surface
+    body: EntityBody,
+    origin_x: i32,
+    origin_y: i32,
+    // Top before this frame's move, so a rider can still be found after the lift moved away from them
+    prev_y: i32fx8,
+    partner: Option<usize>,
+    carrying: bool,
+    state: LiftState,
+}
+
+/// The lift under the player after this frame's lift movement
+pub struct LiftSupport {
+    pub idx: usize,
+    pub top: i32fx8,
+    pub vel_x: i32fx8,
+}
+
+impl Lift {
+    /// Lift whose top is on the given row (8px units) and whose left edge is at col (16px units)
+    pub fn new(kind: LiftKind, row: usize, col: usize) -> Self {
+        let origin_x = (col << 4) as i32;
+        let origin_y = (row << 3) as i32;
+        let mut body = EntityBody::new(
+            i32fx8::wrapping_from(origin_x),
+            i32fx8::wrapping_from(origin_y),
+        );
+        match kind {
+            LiftKind::Horizontal { .. } => body.vel_x = LIFT_SPEED,
+            LiftKind::Vertical { .. } => body.vel_y = -LIFT_SPEED,
+            LiftKind::Falling | LiftKind::Balance { .. } => {}
+        }
+
+        Lift {
+            kind,
+            body,
+            origin_x,
+            origin_y,
+            prev_y: body.y,
+            partner: None,
+            carrying: false,
+            state: LiftState::Moving,
+        }
+    }
+
+    /// Returns false once the lift has dropped out of the level
+    fn tick(&mut self) -> bool {
+        self.prev_y = self.body.y;
+
+        match (self.state, self.kind) {
+            (LiftState::Dropping, _) => self.body.apply_gravity(),
+            (LiftState::Moving, LiftKind::Horizontal { range }) => {
+                let x = self.body.px_x();
+                let end = self.origin_x + ((range as i32) << 4);
+                let is_moving_right = self.body.vel_x > i32fx8::default();
+                if (is_moving_right && x >= end) || (!is_moving_right && x <= self.origin_x) {
+                    self.body.vel_x = -self.body.vel_x;
+                }
+            }
+            (LiftState::Moving, LiftKind::Vertical { range }) => {
+                let y = self.body.px_y();
+                let top = self.origin_y - ((range as i32) << 4);
+                let is_moving_up = self.body.vel_y < i32fx8::default();
+                if (is_moving_up && y <= top) || (!is_moving_up && y >= self.origin_y) {
+                    self.body.vel_y = -self.body.vel_y;
+                }
+            }
+            (LiftState::Moving, LiftKind::Falling) => {
+                self.body.vel_y = if self.carrying {
+                    FALL_SPEED
+                } else {
+                    i32fx8::default()
+                };
+            }
+            // Balance lifts are driven in pairs by the manager
+            (LiftState::Moving, LiftKind::Balance { .. }) => {}
+        }
+
+        !self.body.step_free()
+    }
+
+    fn is_offscreen(&self, screen: &ScreenInfo) -> bool {
+        let (x, _) = self.body.to_screenspace(screen);
+        x + LIFT_WIDTH < 0
+    }
+
+    fn post_tick(&self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Horizontal)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.1 = otr.1.with_size(0);
+        otr.2 = otr
+            .2
+            .with_tile_id(LIFT_TILE_IDX_START as u16 * 2)
+            .with_priority(0)
+            .with_palbank(0);
+        otr.set_y(y.clamp(-8, 160) as u16);
+
+        for segment in 0..LIFT_SEGMENTS {
+            let seg_x = x + ((segment as i32) << 4);
+            if seg_x < -16 || seg_x >= 240 {
+                OamManager::hide(oam_idx + segment);
+                continue;
+            }
+            otr.set_x(seg_x as u16);
+            OamManager::write(oam_idx + segment, otr);
+        }
+    }
+}
+
+pub struct LiftManager {
+    lifts: FixedBag<Lift, MAX_LIFTS>,
+}
+
+impl LiftManager {
+    pub const fn new() -> Self {
+        LiftManager {
+            lifts: FixedBag::new(),
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.lifts.clear();
+        for idx in 0..MAX_LIFTS * LIFT_SEGMENTS {
+            OamManager::hide(LIFT_OAM_START + idx);
+        }
+    }
+
+    pub fn on_start() {
+        Lifts.init();
+    }
+
+    /// Adds the lift for a level item, balance lifts also add their partner `partner_offset` columns over
+    pub fn spawn(kind: LiftKind, row: usize, col: usize) -> Result<usize, LiftKind> {
+        let manager = Lifts.assume_init();
+        let LiftKind::Balance { partner_offset } = kind else {
+            return manager
+                .lifts
+                .push(Lift::new(kind, row, col))
+                .map_err(|lift| lift.kind);
+        };
+
+        let used_slots = manager.lifts.iter().count();
+        if MAX_LIFTS - used_slots < 2 {
+            return Err(kind);
+        }
+        let left = manager
+            .lifts
+            .push(Lift::new(kind, row, col))
+            .map_err(|lift| lift.kind)?;
+        let right = manager
+            .lifts
+            .push(Lift::new(kind, row, col + partner_offset as usize))
+            .map_err(|lift| lift.kind)?;
+        if let Some(lift) = manager.lifts.get_mut(left) {
+            lift.partner = Some(right);
+        }
+        if let Some(lift) = manager.lifts.get_mut(right) {
+            lift.partner = Some(left);
+        }
+        Ok(left)
+    }
+
+    /// Finds the lift the player's feet are resting on or would pass through this frame
+    pub fn support(player: &Hitbox, vel_y: i32fx8) -> Option<LiftSupport> {
+        if vel_y < i32fx8::default() {
+            return None;
+        }
+
+        let manager = Lifts.assume_init();
+        // Players stand 1px into whatever they are standing on
+        let feet = player.bottom() - 1;
+        let fall = (vel_y.to_bits() >> 8) + 1;
+        for (idx, lift) in manager.lifts.iter() {
+            let x = lift.body.px_x();
+            // Same leeway as the tile collision, the edges of the player don't count
+            let is_above = player.x + 3 < x + LIFT_WIDTH && x < player.x + player.w - 3;
+            let top = lift.body.px_y();
+            let prev_top = lift.prev_y.to_bits() >> 8;
+            let is_on_top = feet + fall >= top.min(prev_top) && feet <= top.max(prev_top) + fall;
+            if is_above && is_on_top {
+                return Some(LiftSupport {
+                    idx,
+                    top: lift.body.y,
+                    vel_x: lift.body.vel_x,
+                });
+            }
+        }
+        None
+    }
+
+    /// Marks a lift as ridden for its next tick, which is what makes falling and balance lifts move
+    pub fn ride(idx: usize) {
+        if let Some(lift) = Lifts.assume_init().lifts.get_mut(idx) {
+            lift.carrying = true;
+        }
+    }
+
+    /// Pulls the ridden side of a balance pair down and the other side up
+    fn drive_balance(&mut self, idx: usize, partner: usize) {
+        let (Some(lift), Some(other)) = (self.lifts.get(idx), self.lifts.get(partner)) else {
+            return;
+        };
+
+        let vel_y = match (lift.carrying, other.carrying) {
+            (true, false) => BALANCE_SPEED,
+            (false, true) => -BALANCE_SPEED,
+            _ => i32fx8::default(),
+        };
+        let rope_snapped = lift.body.px_y() <= lift.origin_y - BALANCE_TRAVEL
+            || other.body.px_y() <= other.origin_y - BALANCE_TRAVEL;
+
+        for (lift_idx, vel_y) in [(idx, vel_y), (partner, -vel_y)] {
+            let Some(lift) = self.lifts.get_mut(lift_idx) else {
+                continue;
+            };
+            if rope_snapped {
+                lift.state = LiftState::Dropping;
+                lift.body.vel_y = i32fx8::default();
+            } else {
+                lift.body.vel_y = vel_y;
+            }
+        }
+    }
+
+    pub fn tick(_tick: TickContext) {
+        let manager = Lifts.assume_init();
+        let screen = ScreenManager::get_screen_info();
+
+        for idx in 0..MAX_LIFTS {
+            let Some(lift) = manager.lifts.get(idx) else {
+                continue;
+            };
+            if let (LiftState::Moving, LiftKind::Balance { .. }, Some(partner)) =
+                (lift.state, lift.kind, lift.partner)
+                && idx < partner
+            {
+                manager.drive_balance(idx, partner);
+            }
+        }
+
+        let mut removed_partners = [None; MAX_LIFTS];
+        for (idx, slot) in manager.lifts.iter_mut_opt() {
+            let Some(lift) = slot else {
+                continue;
+            };
+
+            if !lift.tick() || lift.is_offscreen(&screen) {
+                removed_partners[idx] = lift.partner;
+                *slot = None;
+                continue;
+            }
+            // The player marks the lift again during its own tick if still standing on it
+            lift.carrying = false;
+        }
+
+        // A balance lift can't work without the other side of its rope
+        for partner in removed_partners.into_iter().flatten() {
+            manager.lifts.remove(partner);
+        }
+    }
+
+    pub fn post_tick() {
+        let manager = Lifts.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        for idx in 0..MAX_LIFTS {
+            let oam_idx = LIFT_OAM_START + idx * LIFT_SEGMENTS;
+            match manager.lifts.get(idx) {
+                Some(lift) => lift.post_tick(&screen, oam_idx),
+                None => {
+                    for segment in 0..LIFT_SEGMENTS {
+                        OamManager::hide(oam_idx + segment);
+                    }
+                }
+            }
+        }
+    }
+}
+
+unsafe impl StaticInitSafe for LiftManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Lifts: LiftManager = LiftManager::new());
@@ -6,15 +6,24 @@ use core::fmt::Write;
 use gba::prelude::*;
 use mario::{
     assets::{AFFINE2_SCREENBLOCK_START, AssetManager, TEXT_SCREENBLOCK_START},
+    castle::CastleManager,
     effects::EffectsManager,
+    enemies::EnemyManager,
+    firebars::FirebarManager,
     gba_warning,
+    items::ItemManager,
     keys::KeysManager,
     level_manager::LevelManager,
+    lifts::LiftManager,
     logger,
+    oam::OamManager,
     player::PlayerManager,
+    projectiles::ProjectileManager,
     screen::ScreenManager,
+    spawners::SpawnerManager,
     tick::TickContext,
     topbar::TopBarManager,
+    transition::{Transition, TransitionManager},
 };
 
 #[panic_handler]
@@ -66,21 +75,38 @@ extern "C" fn main() -> ! {
             .with_screenblock(AFFINE2_SCREENBLOCK_START as u16)
             .with_bpp8(true)
             .with_charblock(0)
+            .with_priority(1)
             .with_mosaic(true)
             .with_is_affine_wrapping(true),
     );
 
     KeysManager::on_start();
+    OamManager::on_start();
     AssetManager::on_start();
     ScreenManager::on_start();
     PlayerManager::on_start();
     LevelManager::on_start();
     EffectsManager::on_start();
+    EnemyManager::on_start();
+    ItemManager::on_start();
+    LiftManager::on_start();
+    FirebarManager::on_start();
+    ProjectileManager::on_start();
+    SpawnerManager::on_start();
+    CastleManager::on_start();
+    TransitionManager::on_start();
+
+    // Level intro
+    TransitionManager::start(Transition::FadeFromBlack, 30, None);
 
     let mut loop_counter: u32 = 0;
 
     loop {
         VBlankIntrWait();
+        // Last frame's sprites and scroll go out together before anything starts drawing
+        OamManager::on_vblank();
+        ScreenManager::on_vblank();
+        TransitionManager::on_vblank();
         let keys = KeysManager::on_vblank();
         let tick_ctx = TickContext {
             tick_count: loop_counter,
@@ -98,32 +124,52 @@ extern "C" fn main() -> ! {
         TIMER1_CONTROL.write(TimerControl::new().with_enabled(true).with_cascade(true));
 
         LevelManager::tick(tick_ctx);
+        // Lifts move before the player so riders get carried by this frame's movement
+        LiftManager::tick(tick_ctx);
         let after_lvlmgr: u16 = TIMER0_COUNT.read();
         PlayerManager::tick(tick_ctx);
         let after_pmgr: u16 = TIMER0_COUNT.read();
+        SpawnerManager::tick(tick_ctx);
+        EnemyManager::tick(tick_ctx);
+        ItemManager::tick(tick_ctx);
+        FirebarManager::tick(tick_ctx);
+        ProjectileManager::tick(tick_ctx);
+        CastleManager::tick(tick_ctx);
+        TransitionManager::tick(tick_ctx);
+        let after_enmmgr: u16 = TIMER0_COUNT.read();
         TopBarManager::tick(tick_ctx);
         let after_tbmgr: u16 = TIMER0_COUNT.read();
         EffectsManager::tick(tick_ctx);
         let after_effmgr: u16 = TIMER0_COUNT.read();
 
-        ScreenManager::post_tick();
-        let after_scrmgr: u16 = TIMER0_COUNT.read();
+        EnemyManager::post_tick();
+        ItemManager::post_tick();
+        LiftManager::post_tick();
+        FirebarManager::post_tick();
+        ProjectileManager::post_tick();
+        let after_enmmgr_post: u16 = TIMER0_COUNT.read();
         AssetManager::post_tick();
         let after_astmgr: u16 = TIMER0_COUNT.read();
         EffectsManager::post_tick(tick_ctx);
+        ScreenManager::post_tick();
+        TransitionManager::post_tick();
         let after_effmgr_post: u16 = TIMER0_COUNT.read();
 
         // let after0 = TIMER0_COUNT.read();
         // let after1 = TIMER1_COUNT.read();
+        let effect_stats = EffectsManager::stats();
         gba_warning!(
-            "LvlMgr={:4} PMgr={:4} TBMgr={:4} EffMgr={:4} ScrMgr={:4} AstMgr={:4} EffMgrPost={:4}",
+            "LvlMgr={:4} PMgr={:4} EnmMgr={:4} TBMgr={:4} EffMgr={:4} EnmMgrPost={:4} AstMgr={:4} EffMgrPost={:4} EffDropped={} EffDeferred={}",
             after_lvlmgr,
             after_pmgr - after_lvlmgr,
-            after_tbmgr - after_pmgr,
+            after_enmmgr - after_pmgr,
+            after_tbmgr - after_enmmgr,
             after_effmgr - after_tbmgr,
-            after_scrmgr - after_effmgr,
-            after_astmgr - after_scrmgr,
+            after_enmmgr_post - after_effmgr,
+            after_astmgr - after_enmmgr_post,
             after_effmgr_post - after_astmgr,
+            effect_stats.dropped,
+            effect_stats.deferred,
         );
         TIMER0_CONTROL.write(TimerControl::new());
         TIMER1_CONTROL.write(TimerControl::new());
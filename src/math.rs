@@ -1,5 +1,7 @@
 use core::ops::Deref;
 
+use gba::prelude::*;
+
 use crate::fixed_string::FixedString;
 
 #[repr(u16)]
@@ -69,3 +71,48 @@ pub fn div10_u16(n: u16) -> (u16, u16) {
     let r = n - q * 10;
     (q, r)
 }
+
+// sin(i / 256 of a turn) * 256 for the first quarter turn, the other quarters mirror it
+const SIN_QUARTER: [i16; 65] = [
+    0, 6, 13, 19, 25, 31, 38, 44, 50, 56, 62, 68, 74, 80, 86, 92, 98, 104, 109, 115, 121, 126, 132,
+    137, 142, 147, 152, 157, 162, 167, 172, 177, 181, 185, 190, 194, 198, 202, 206, 209, 213, 216,
+    220, 223, 226, 229, 231, 234, 237, 239, 241, 243, 245, 247, 248, 250, 251, 252, 253, 254, 255,
+    255, 256, 256, 256,
+];
+
+/// Sine of an angle in 256ths of a turn, so angles wrap for free as a u8
+pub fn sin_u8(angle: u8) -> i32fx8 {
+    let idx = (angle & 0x3F) as usize;
+    let value = match angle >> 6 {
+        0 => SIN_QUARTER[idx],
+        1 => SIN_QUARTER[64 - idx],
+        2 => -SIN_QUARTER[idx],
+        _ => -SIN_QUARTER[64 - idx],
+    };
+    i32fx8::from_bits(value as i32)
+}
+
+/// Cosine of an angle in 256ths of a turn
+pub fn cos_u8(angle: u8) -> i32fx8 {
+    sin_u8(angle.wrapping_add(64))
+}
+
+/// Integer square root, rounded down
+pub fn isqrt_u32(n: u32) -> u32 {
+    let mut rem = n;
+    let mut root = 0;
+    let mut bit = 1 << 30;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
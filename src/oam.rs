@@ -0,0 +1,294 @@
+use gba::prelude::*;
+
+use crate::{
+    ewram_static, gba_warning,
+    math::{cos_u8, sin_u8},
+    screen::ScreenManager,
+    static_init::StaticInitSafe,
+};
+
+const OAM_ENTRIES: usize = 128;
+const AFFINE_GROUPS: usize = OAM_ENTRIES / 4;
+// Everything before this is a fixed block owned by one manager, the rest is handed out through handles
+pub const DYNAMIC_OAM_START: usize = 32;
+
+const HIDDEN: ObjAttr = ObjAttr(
+    ObjAttr0::new().with_style(ObjDisplayStyle::NotDisplayed),
+    ObjAttr1::new(),
+    ObjAttr2::new(),
+);
+
+/// One OAM entry as laid out in hardware, every fourth halfword is a piece of an affine matrix
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ShadowEntry {
+    attr: ObjAttr,
+    affine: i16fx8,
+}
+
+#[repr(C, align(4))]
+struct ShadowOam([ShadowEntry; OAM_ENTRIES]);
+
+/// Owns a dynamically allocated OAM slot, the slot is hidden and freed on drop
+pub struct OamHandle {
+    idx: usize,
+}
+
+impl OamHandle {
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    pub fn write(&self, otr: ObjAttr) {
+        OamManager::write(self.idx, otr);
+    }
+
+    pub fn hide(&self) {
+        OamManager::hide(self.idx);
+    }
+}
+
+impl Drop for OamHandle {
+    fn drop(&mut self) {
+        let manager = Oam.assume_init();
+        manager.shadow.0[self.idx].attr = HIDDEN;
+        manager.free_bits[self.idx >> 5] &= !(1u32 << (self.idx & 31));
+    }
+}
+
+/// Scale, rotation and mirroring for an affine sprite, turned into a matrix by [`AffineHandle::set`]
+#[derive(Clone, Copy)]
+pub struct Transform {
+    // Negative scales mirror on that axis
+    pub scale_x: i32fx8,
+    pub scale_y: i32fx8,
+    // 256ths of a turn
+    pub rotation: u8,
+    // Mirrors horizontally on top of the scale
+    pub flip: bool,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        scale_x: i32fx8::from_bits(1 << 8),
+        scale_y: i32fx8::from_bits(1 << 8),
+        rotation: 0,
+        flip: false,
+    };
+
+    pub const fn flipped(flip: bool) -> Self {
+        Transform {
+            flip,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// The pa, pb, pc, pd matrix, which maps screen pixels back into the sprite so it holds inverse scales
+    fn params(self) -> [i16fx8; 4] {
+        let mut inv_x = inverse(self.scale_x);
+        let inv_y = inverse(self.scale_y);
+        if self.flip {
+            inv_x = -inv_x;
+        }
+        let cos = cos_u8(self.rotation).to_bits();
+        let sin = sin_u8(self.rotation).to_bits();
+        [
+            (cos * inv_x) >> 8,
+            (-sin * inv_x) >> 8,
+            (sin * inv_y) >> 8,
+            (cos * inv_y) >> 8,
+        ]
+        .map(|bits| i16fx8::from_bits(bits.clamp(i16::MIN as i32, i16::MAX as i32) as i16))
+    }
+}
+
+/// 1 / scale with 8 fractional bits, a zero scale shrinks the sprite as far as it goes
+fn inverse(scale: i32fx8) -> i32 {
+    match scale.to_bits() {
+        0 => i16::MAX as i32,
+        bits => (1 << 16) / bits,
+    }
+}
+
+/// Shares one of the 32 affine parameter groups, cloning adds a reference and the group is freed
+/// when the last one drops
+pub struct AffineHandle {
+    idx: usize,
+}
+
+impl AffineHandle {
+    /// Value for `ObjAttr1::with_affine_index`
+    pub fn idx(&self) -> u16 {
+        self.idx as u16
+    }
+
+    pub fn set(&self, transform: Transform) {
+        OamManager::set_affine(self.idx, transform.params());
+    }
+}
+
+impl Clone for AffineHandle {
+    fn clone(&self) -> Self {
+        Oam.assume_init().affine_refs[self.idx] += 1;
+        AffineHandle { idx: self.idx }
+    }
+}
+
+impl Drop for AffineHandle {
+    fn drop(&mut self) {
+        let manager = Oam.assume_init();
+        manager.affine_refs[self.idx] -= 1;
+        if manager.affine_refs[self.idx] == 0 {
+            manager.set_affine_internal(self.idx, Transform::IDENTITY.params());
+        }
+    }
+}
+
+/// Sprites are drawn into a shadow copy of OAM during the frame and copied over in one DMA at VBlank
+pub struct OamManager {
+    shadow: ShadowOam,
+    // Set bits are taken, the fixed blocks are marked taken up front
+    free_bits: [u32; OAM_ENTRIES / 32],
+    affine_refs: [u8; AFFINE_GROUPS],
+    mosaic: bool,
+}
+
+impl OamManager {
+    pub const fn new() -> Self {
+        OamManager {
+            shadow: ShadowOam(
+                [ShadowEntry {
+                    attr: HIDDEN,
+                    affine: i16fx8::from_bits(0),
+                }; OAM_ENTRIES],
+            ),
+            free_bits: [0; OAM_ENTRIES / 32],
+            affine_refs: [0; AFFINE_GROUPS],
+            mosaic: false,
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        for entry in self.shadow.0.iter_mut() {
+            entry.attr = HIDDEN;
+        }
+        self.free_bits = [0; OAM_ENTRIES / 32];
+        for idx in 0..DYNAMIC_OAM_START {
+            self.free_bits[idx >> 5] |= 1 << (idx & 31);
+        }
+        self.affine_refs = [0; AFFINE_GROUPS];
+        self.mosaic = false;
+        for group in 0..AFFINE_GROUPS {
+            self.set_affine_internal(group, Transform::IDENTITY.params());
+        }
+        self.flush();
+    }
+
+    pub fn on_start() {
+        Oam.init();
+    }
+
+    /// Hands out the first free slot after the fixed blocks
+    pub fn alloc() -> Option<OamHandle> {
+        let manager = Oam.assume_init();
+        for (word_idx, word) in manager.free_bits.iter_mut().enumerate() {
+            if *word == u32::MAX {
+                continue;
+            }
+            let bit = (!*word).trailing_zeros() as usize;
+            *word |= 1 << bit;
+            return Some(OamHandle {
+                idx: (word_idx << 5) + bit,
+            });
+        }
+        gba_warning!("Out of OAM slots");
+        None
+    }
+
+    /// Hands out an unused affine parameter group, starting out as the identity
+    pub fn alloc_affine() -> Option<AffineHandle> {
+        let manager = Oam.assume_init();
+        let Some(idx) = manager.affine_refs.iter().position(|refs| *refs == 0) else {
+            gba_warning!("Out of affine groups");
+            return None;
+        };
+        manager.affine_refs[idx] = 1;
+        Some(AffineHandle { idx })
+    }
+
+    pub fn write(idx: usize, mut otr: ObjAttr) {
+        let manager = Oam.assume_init();
+        if manager.mosaic {
+            otr.0 = otr.0.with_mosaic(true);
+        }
+        manager.shadow.0[idx].attr = otr;
+    }
+
+    /// Turns on the mosaic flag of every sprite written from now on, on top of the ones that set it
+    /// themselves
+    pub fn set_mosaic(mosaic: bool) {
+        Oam.assume_init().mosaic = mosaic;
+    }
+
+    pub fn hide(idx: usize) {
+        Self::write(idx, HIDDEN);
+    }
+
+    fn set_affine(group: usize, params: [i16fx8; 4]) {
+        Oam.assume_init().set_affine_internal(group, params);
+    }
+
+    fn set_affine_internal(&mut self, group: usize, params: [i16fx8; 4]) {
+        for (i, param) in params.into_iter().enumerate() {
+            self.shadow.0[group * 4 + i].affine = param;
+        }
+    }
+
+    /// Copies the shadow into OAM, must run during VBlank
+    pub fn on_vblank() {
+        let manager = Oam.assume_init();
+        // Shaking moves every sprite against the background, the shadow keeps the real positions
+        // so the offset comes back off once the copy is done
+        let (x, y) = ScreenManager::shake_offset();
+        let is_shaking = x != 0 || y != 0;
+        if is_shaking {
+            manager.offset_displayed(-x, -y);
+        }
+        manager.flush();
+        if is_shaking {
+            manager.offset_displayed(x, y);
+        }
+    }
+
+    fn offset_displayed(&mut self, x: i32, y: i32) {
+        for entry in self.shadow.0.iter_mut() {
+            let otr = &mut entry.attr;
+            if otr.0.style() == ObjDisplayStyle::NotDisplayed {
+                continue;
+            }
+            otr.set_x(otr.1.x().wrapping_add(x as u16));
+            otr.set_y(otr.0.y().wrapping_add(y as u16));
+        }
+    }
+
+    fn flush(&self) {
+        unsafe {
+            DMA3_SRC.write(self.shadow.0.as_ptr().cast());
+            DMA3_DEST.write(OBJ_ATTR_ALL.index(0).as_usize() as *mut _);
+            DMA3_COUNT.write((size_of::<ShadowOam>() / 4) as u16);
+            DMA3_CONTROL.write(
+                DmaControl::new()
+                    .with_transfer_32bit(true)
+                    .with_enabled(true),
+            );
+        }
+    }
+}
+
+unsafe impl StaticInitSafe for OamManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Oam: OamManager = OamManager::new());
@@ -2,21 +2,39 @@ use gba::prelude::*;
 
 use crate::{
     assets::{MARIO_TILE, MARIO_TILE_IDX_START},
+    castle::CastleManager,
     effects::{
-        EffectsManager,
+        Effect, EffectPriority, EffectsManager,
+        brick_debris::BrickDebris,
         coin_up::CoinUp,
-        points::{Points, ScoreAmount},
+        mosaic::Mosaic,
+        points::ScoreAmount,
+        springboard::Springboard,
         tile_bounce::{BounceEffectTile, TileBounce},
+        timeline::Timeline,
     },
+    entity::Hitbox,
     ewram_static, gba_error, gba_warning,
-    level_manager::{LevelManager, is_tile},
-    levels::shared::{BRICK, QUESTION_BLOCK_UNUSED, QUESTION_BLOCK_USED},
-    math::mod_mask_u32,
+    items::ItemManager,
+    level_manager::{LevelManager, clear_tile, is_tile},
+    levels::shared::{AXE, BRICK, COIN, QUESTION_BLOCK_UNUSED, QUESTION_BLOCK_USED, SPRINGBOARD},
+    lifts::LiftManager,
+    math::{Powers, mod_mask_u32},
+    oam::{AffineHandle, OamManager, Transform},
+    scoring,
     screen::{ScreenInfo, ScreenManager},
     static_init::StaticInitSafe,
     tick::TickContext,
+    transition::{Transition, TransitionManager},
 };
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PowerState {
+    Small,
+    // Twice as tall, drawn by stretching the small frames
+    Big,
+}
+
 pub struct PlayerManager {
     otr: ObjAttr,
     player_x: i32fx8,
@@ -25,6 +43,24 @@ pub struct PlayerManager {
     vel_y: i32fx8,
     next_anim_tick: u8,
     facing_dir: bool, // true is right, false is left
+    stomp_chain: u8,  // Enemies landed on since last touching the ground
+    power: PowerState,
+    // Whether hitting a brick from below shatters it instead of bouncing it
+    can_break_bricks: bool,
+    star_ticks: u16,
+    invulnerable_ticks: u8,
+    spring: Option<SpringHold>,
+    // Set while a cutscene has taken control away from the player
+    frozen: bool,
+    affine: Option<AffineHandle>,
+}
+
+// Player pinned to a springboard while it compresses, the Springboard effect drives it
+#[derive(Clone, Copy)]
+struct SpringHold {
+    base_y: i32fx8,
+    depth: i32,
+    super_jump: bool,
 }
 
 unsafe impl StaticInitSafe for PlayerManager {
@@ -53,6 +89,21 @@ enum MarioAnimationTileIdx {
 const VERT_DIFF_UP: i32fx8 = i32fx8::from_bits(64);
 const VERT_DIFF_DOWN: i32fx8 = i32fx8::from_bits(128);
 
+const SPRING_LAUNCH: i32fx8 = i32fx8::from_bits(-1536);
+const SPRING_SUPER_LAUNCH: i32fx8 = i32fx8::from_bits(-2304);
+
+const STAR_TICKS: u16 = 600;
+const INVULNERABLE_TICKS: u8 = 120;
+const DEATH_MOSAIC_TICKS: u32 = 48;
+const DEATH_IRIS_CLOSE_TICKS: u16 = 60;
+const RESPAWN_IRIS_OPEN_TICKS: u16 = 30;
+
+fn add_block_effect(effect: Effect, priority: EffectPriority) {
+    if EffectsManager::add_effect(effect, 0, priority).is_err() {
+        gba_warning!("Effect queue full, dropped block effect");
+    }
+}
+
 impl PlayerManager {
     pub const fn new() -> Self {
         PlayerManager {
@@ -63,6 +114,14 @@ impl PlayerManager {
             vel_x: i32fx8::wrapping_from(0),
             player_y: i32fx8::wrapping_from(32),
             vel_y: i32fx8::wrapping_from(0),
+            stomp_chain: 0,
+            power: PowerState::Small,
+            can_break_bricks: false,
+            star_ticks: 0,
+            invulnerable_ticks: 0,
+            spring: None,
+            frozen: false,
+            affine: None,
         }
     }
 
@@ -95,6 +154,19 @@ impl PlayerManager {
         (self.player_y.to_bits() >> (8 + 3)) as u16
     }
 
+    /// Big Mario grows upwards from the same feet, `player_y` stays the top of the lower 16px
+    fn head_px(&self) -> i32 {
+        match self.power {
+            PowerState::Small => 0,
+            PowerState::Big => 16,
+        }
+    }
+
+    /// Row of the top of the player's head
+    fn top_row(&self) -> u16 {
+        self.row().saturating_sub((self.head_px() >> 3) as u16)
+    }
+
     fn col(&self) -> u16 {
         (self.player_x.to_bits() >> (8 + 3)) as u16
     }
@@ -124,15 +196,23 @@ impl PlayerManager {
         }
     }
 
-    fn update_face_dir(&mut self) {
-        AFFINE_PARAM_A.index(0).write(if self.facing_dir {
-            i16fx8::from_bits(1 << 8)
-        } else {
-            -i16fx8::from_bits(1 << 8)
-        });
+    fn update_transform(&mut self) {
+        // Art faces right
+        let mut transform = Transform::flipped(!self.facing_dir);
+        if self.power == PowerState::Big {
+            transform.scale_y = i32fx8::from_bits(2 << 8);
+        }
+        if let Some(affine) = &self.affine {
+            affine.set(transform);
+        }
     }
 
     fn reset_internal(&mut self) {
+        if self.affine.is_none() {
+            self.affine = OamManager::alloc_affine();
+        }
+        let affine_idx = self.affine.as_ref().map_or(0, AffineHandle::idx);
+
         let mut otr = ObjAttr::new();
         otr.set_x(32);
         otr.set_y(32);
@@ -142,13 +222,8 @@ impl PlayerManager {
             .with_shape(ObjShape::Square)
             .with_mode(ObjEffectMode::Normal)
             .with_bpp8(true);
-        otr.1 = otr.1.with_size(1).with_affine_index(0);
-        OBJ_ATTR_ALL.index(0).write(otr);
-
-        AFFINE_PARAM_A.index(0).write(i16fx8::from_bits(1 << 8));
-        AFFINE_PARAM_B.index(0).write(i16fx8::from_bits(0));
-        AFFINE_PARAM_C.index(0).write(i16fx8::from_bits(0));
-        AFFINE_PARAM_D.index(0).write(i16fx8::from_bits(1 << 8));
+        otr.1 = otr.1.with_size(1).with_affine_index(affine_idx);
+        OamManager::write(0, otr);
         self.otr = otr;
         self.set_tile(MarioAnimationTileIdx::Standing);
         self.next_anim_tick = 0;
@@ -157,14 +232,182 @@ impl PlayerManager {
         self.player_x = i32fx8::wrapping_from(32);
         self.player_y = i32fx8::wrapping_from(32);
         self.facing_dir = true;
+        self.stomp_chain = 0;
+        self.power = PowerState::Small;
+        self.can_break_bricks = false;
+        self.star_ticks = 0;
+        self.invulnerable_ticks = 0;
+        self.spring = None;
+        self.frozen = false;
     }
 
     pub fn on_start() {
         Player.init();
     }
 
+    fn body_hitbox(&self) -> Hitbox {
+        Hitbox {
+            x: self.player_x.to_bits() >> 8,
+            y: (self.player_y.to_bits() >> 8) - self.head_px(),
+            w: 16,
+            h: 16 + self.head_px(),
+        }
+    }
+
+    /// None while dying so enemies and items stop interacting with the player
+    pub fn hitbox() -> Option<Hitbox> {
+        let manager = Player.assume_init();
+        if manager.get_tile() == MarioAnimationTileIdx::DieState {
+            return None;
+        }
+        Some(manager.body_hitbox())
+    }
+
+    pub fn is_falling() -> bool {
+        Player.assume_init().is_moving_down()
+    }
+
+    /// Bounce up after landing on an enemy, higher if A is held, without scoring
+    pub fn bounce(a_held: bool) {
+        let manager = Player.assume_init();
+        if a_held {
+            // Same as a jump, holding A keeps extending it
+            manager.vel_y = i32fx8::from_bits(-1100);
+            manager.next_anim_tick = 0;
+        } else {
+            manager.vel_y = i32fx8::from_bits(-768);
+            manager.next_anim_tick = u8::MAX;
+        }
+        manager.set_tile(MarioAnimationTileIdx::Jumping1);
+    }
+
+    /// Bounce off a stomped enemy, returning the score for this stomp in the current chain
+    pub fn stomp_bounce(a_held: bool) -> ScoreAmount {
+        PlayerManager::bounce(a_held);
+        let manager = Player.assume_init();
+        let amount = ScoreAmount::from_chain(manager.stomp_chain);
+        manager.stomp_chain = manager.stomp_chain.saturating_add(1);
+        amount
+    }
+
+    /// Big Mario shrinks and blinks for a bit, small Mario dies
+    pub fn damage() {
+        let manager = Player.assume_init();
+        if manager.star_ticks > 0 || manager.invulnerable_ticks > 0 {
+            return;
+        }
+
+        if manager.power == PowerState::Big {
+            manager.power = PowerState::Small;
+            manager.can_break_bricks = false;
+            manager.invulnerable_ticks = INVULNERABLE_TICKS;
+            return;
+        }
+
+        manager.vel_x = i32fx8::default();
+        manager.set_tile(MarioAnimationTileIdx::DieState);
+        manager.next_anim_tick = 0;
+    }
+
+    pub fn grow() {
+        let manager = Player.assume_init();
+        manager.power = PowerState::Big;
+        manager.can_break_bricks = true;
+    }
+
+    pub fn grant_star() {
+        Player.assume_init().star_ticks = STAR_TICKS;
+    }
+
+    /// While starred every enemy the player touches gets knocked out
+    pub fn is_star() -> bool {
+        Player.assume_init().star_ticks > 0
+    }
+
+    pub fn power() -> PowerState {
+        Player.assume_init().power
+    }
+
+    /// Sinks the player with the top of the springboard they are standing on
+    pub fn spring_compress(depth: i32) {
+        if let Some(spring) = &mut Player.assume_init().spring {
+            spring.depth = depth;
+        }
+    }
+
+    /// Throws the player off the springboard, much higher if A was pressed while it was compressed
+    pub fn spring_launch() {
+        let manager = Player.assume_init();
+        let Some(spring) = manager.spring.take() else {
+            return;
+        };
+        manager.player_y = spring.base_y;
+        manager.vel_y = if spring.super_jump {
+            SPRING_SUPER_LAUNCH
+        } else {
+            SPRING_LAUNCH
+        };
+        // No extending the launch by holding A
+        manager.next_anim_tick = u8::MAX;
+        manager.set_tile(MarioAnimationTileIdx::Jumping1);
+    }
+
+    /// Stops the player in place and ignores input until unfrozen
+    pub fn freeze(frozen: bool) {
+        let manager = Player.assume_init();
+        manager.frozen = frozen;
+        manager.vel_x = i32fx8::default();
+        manager.vel_y = i32fx8::default();
+    }
+
+    fn spring_handler(&mut self, tick_context: TickContext) {
+        let Some(spring) = &mut self.spring else {
+            return;
+        };
+        if tick_context
+            .keys
+            .is_just_pressed(KeyInput::new().with_a(true))
+        {
+            spring.super_jump = true;
+        }
+        self.player_y = spring.base_y.add(i32fx8::wrapping_from(spring.depth));
+        self.vel_x = i32fx8::default();
+        self.vel_y = i32fx8::default();
+    }
+
+    /// Starts a springboard bounce if the player is standing on one
+    fn check_springboard(&mut self) {
+        let row = self.row() as usize + 2;
+        let col = (self.col_middle() >> 1) as usize;
+        if row + 1 >= 32 || is_tile(row, col, [SPRINGBOARD]).is_none() {
+            return;
+        }
+        let springboard = Springboard::new(row, col).as_effect();
+        if EffectsManager::add_effect(springboard, 0, EffectPriority::High).is_err() {
+            gba_warning!("Effect queue full, springboard skipped");
+            return;
+        }
+        self.spring = Some(SpringHold {
+            base_y: self.player_y,
+            depth: 0,
+            super_jump: false,
+        });
+    }
+
+    /// Middle of the sprite in world pixels
+    fn center_px(&self) -> (i32, i32) {
+        let body = self.body_hitbox();
+        (body.x + body.w / 2, body.y + body.h / 2)
+    }
+
     fn die_state_handler(&mut self) {
         if self.next_anim_tick == 0 {
+            let mosaic = Mosaic::new(DEATH_MOSAIC_TICKS).as_effect();
+            if EffectsManager::add_effect(mosaic, 0, EffectPriority::High).is_err() {
+                gba_warning!("Effect queue full, skipped death mosaic");
+            }
+            let (x, y) = self.center_px();
+            TransitionManager::start(Transition::IrisClose { x, y }, DEATH_IRIS_CLOSE_TICKS, None);
             self.vel_y = i32fx8::from_bits(-1792);
             self.next_anim_tick = 1;
             return;
@@ -181,20 +424,21 @@ impl PlayerManager {
 
         if self.row() > 32 {
             self.reset_internal();
+            let (x, y) = self.center_px();
+            TransitionManager::start(Transition::IrisOpen { x, y }, RESPAWN_IRIS_OPEN_TICKS, None);
             // todo: reset level / game state of sorts
         }
     }
 
     fn default_movement_handler(&mut self, tick_context: TickContext, screen: ScreenInfo) {
-        let is_player_sorta_to_the_right = mod_mask_u32(
-            (self.player_x.to_bits() >> 8) as u32,
-            crate::math::Powers::_8,
-        ) >= 3;
+        let is_player_sorta_to_the_right =
+            mod_mask_u32((self.player_x.to_bits() >> 8) as u32, Powers::_8) >= 3;
 
         let row: u16 = self.row();
+        let top_row = self.top_row();
         let bottom_of_player = row + 2;
-        let mask_above = 0b1 << row.saturating_sub(1);
-        let mask_body = 0b11 << row;
+        let mask_above = 0b1 << top_row.saturating_sub(1);
+        let mask_body = ((0b1 << (bottom_of_player - top_row)) - 1) << top_row;
         let mask_under = 0b1 << bottom_of_player;
 
         let left_air = LevelManager::collision_mask(self.col().saturating_sub(1));
@@ -202,7 +446,19 @@ impl PlayerManager {
         let right_collision = LevelManager::collision_mask(self.col() + 1);
         let right_air = LevelManager::collision_mask(self.col() + 2);
 
-        let collision_bottom = (left_collision & mask_under != 0)
+        // Lifts already moved this frame, ride along with the one under us
+        let lift = LiftManager::support(&self.body_hitbox(), self.vel_y);
+        if let Some(lift) = &lift {
+            LiftManager::ride(lift.idx);
+            self.player_x = self.player_x.add(lift.vel_x);
+        }
+        let ground_y = match &lift {
+            Some(lift) => lift.top.sub(i32fx8::wrapping_from(15)),
+            None => i32fx8::wrapping_from((row << 3) as i32 + 1),
+        };
+
+        let collision_bottom = lift.is_some()
+            || (left_collision & mask_under != 0)
             || (right_collision & mask_under != 0)
             || (is_player_sorta_to_the_right && (right_air & mask_under != 0));
 
@@ -244,12 +500,16 @@ impl PlayerManager {
             }
             self.player_y = self.player_y.add(self.vel_y);
         } else if collision_bottom && self.is_moving_down() {
-            self.player_y = i32fx8::wrapping_from((self.row() << 3) as i32 + 1);
+            self.player_y = ground_y;
             self.vel_y = i32fx8::wrapping_from(0);
+            self.stomp_chain = 0;
+            if lift.is_none() {
+                self.check_springboard();
+            }
         } else if collision_top && self.is_moving_up() {
             self.player_y = i32fx8::wrapping_from((self.row() << 3) as i32);
             self.vel_y = i32fx8::wrapping_from(0);
-            let row = self.row().saturating_sub(2) as usize;
+            let row = self.top_row().saturating_sub(2) as usize;
             let col = (self.col_middle() >> 1) as usize;
 
             if let Some(tile) = is_tile(
@@ -257,21 +517,37 @@ impl PlayerManager {
                 col,
                 [BRICK, QUESTION_BLOCK_UNUSED, QUESTION_BLOCK_USED],
             ) {
-                if tile == BRICK {
-                    EffectsManager::add_effect(
-                        TileBounce::new(row, col, BounceEffectTile::Brick).as_effect(),
-                        0,
-                    );
+                let block_item = if tile == QUESTION_BLOCK_USED {
+                    None
+                } else {
+                    LevelManager::take_block_item(row, col)
+                };
+
+                if let Some(item) = block_item {
+                    let bounce = TileBounce::new(row, col, BounceEffectTile::UsedBlock);
+                    add_block_effect(bounce.as_effect(), EffectPriority::High);
+                    ItemManager::spawn_from_block(item, row, col);
+                } else if tile == BRICK && self.can_break_bricks {
+                    let debris = BrickDebris::new(row, col).as_effect();
+                    if EffectsManager::add_effect(debris, 0, EffectPriority::High).is_ok() {
+                        scoring::brick_broken();
+                    } else {
+                        gba_warning!("Effect queue full, brick left unbroken");
+                    }
+                } else if tile == BRICK {
+                    let bounce = TileBounce::new(row, col, BounceEffectTile::Brick);
+                    add_block_effect(bounce.as_effect(), EffectPriority::Normal);
                 } else if tile == QUESTION_BLOCK_UNUSED {
-                    EffectsManager::add_effect(
-                        TileBounce::new(row, col, BounceEffectTile::UsedBlock).as_effect(),
-                        0,
-                    );
-                    EffectsManager::add_effect(CoinUp::new(row - 1, col).as_effect(), 0);
-                    EffectsManager::add_effect(
-                        Points::new(row - 2, col, ScoreAmount::OneHundred).as_effect(),
-                        16,
-                    );
+                    let timeline = Timeline::new()
+                        .parallel([
+                            TileBounce::new(row, col, BounceEffectTile::UsedBlock).as_effect(),
+                            CoinUp::new(row - 1, col).as_effect(),
+                        ])
+                        .then(scoring::block_coin_popup(row - 2, col))
+                        .on_complete(scoring::block_coin_award, 0);
+                    if EffectsManager::add_timeline(timeline).is_err() {
+                        gba_warning!("Timelines full, dropped coin block effects");
+                    }
                 } else if tile == QUESTION_BLOCK_USED {
                     // Already used block, do nothing
                 } else {
@@ -279,7 +555,7 @@ impl PlayerManager {
                 }
             }
         } else if self.is_vertically_stationary() {
-            self.player_y = i32fx8::wrapping_from((self.row() << 3) as i32 + 1);
+            self.player_y = ground_y;
             self.vel_y = i32fx8::wrapping_from(0);
             if tick_context
                 .keys
@@ -357,8 +633,9 @@ impl PlayerManager {
 
         if self.vel_y > i32fx8::from_bits(max_y_speed) {
             self.vel_y = i32fx8::from_bits(max_y_speed);
-        } else if self.vel_y < i32fx8::from_bits(-max_y_speed) {
-            self.vel_y = i32fx8::from_bits(-max_y_speed);
+        } else if self.vel_y < SPRING_SUPER_LAUNCH {
+            // Springboards launch faster than a jump ever goes
+            self.vel_y = SPRING_SUPER_LAUNCH;
         }
 
         let is_walking_animation_valid_horizontally: bool = !self.is_horizontally_stationary()
@@ -425,47 +702,76 @@ impl PlayerManager {
         }
     }
 
+    /// Picks up any coin tiles the player overlaps and grabs the axe at the end of a castle
+    fn touch_tiles(&self) {
+        let body = self.body_hitbox();
+        if body.y < 0 {
+            return;
+        }
+
+        // Coins are 16x16 metatiles starting on even 8px rows
+        let top_row = ((body.y >> 4) << 1) as usize;
+        let bottom_row = (((body.y + body.h - 1) >> 4) << 1) as usize;
+        let left_col = (body.x >> 4) as usize;
+        let right_col = ((body.x + 15) >> 4) as usize;
+
+        for row in (top_row..=bottom_row).step_by(2) {
+            if row + 1 >= 32 {
+                continue;
+            }
+            for level_col in [left_col, right_col] {
+                let col = mod_mask_u32(level_col as u32, Powers::_32) as usize;
+                match is_tile(row, col, [COIN, AXE]) {
+                    Some(COIN) => {
+                        clear_tile(row, col);
+                        scoring::coin_collected();
+                    }
+                    Some(_) => {
+                        clear_tile(row, col);
+                        CastleManager::start_finale(row, level_col);
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
     pub fn tick(tick_context: TickContext) {
         let manager = Player.get_or_init();
         let screen = ScreenManager::get_screen_info();
 
         if manager.get_tile() == MarioAnimationTileIdx::DieState {
             manager.die_state_handler();
-        } else {
+        } else if manager.spring.is_some() {
+            manager.spring_handler(tick_context);
+        } else if !manager.frozen {
             manager.default_movement_handler(tick_context, screen);
+            manager.touch_tiles();
         }
 
-        let middle_screen_px = screen.affn_x.add(i32fx8::wrapping_from(10 * 8));
-
-        let player_min_y = screen.affn_y.add(i32fx8::wrapping_from(45));
-        let player_max_y = screen.affn_y.add(i32fx8::wrapping_from(120));
-
-        // gba_warning!("velx {:?}, vely {:?}", manager.vel_x, manager.vel_y);
+        ScreenManager::follow(manager.player_x, manager.player_y, manager.facing_dir);
 
-        let y_diff = if manager.player_y < player_min_y {
-            manager.player_y.sub(player_min_y)
-        } else if manager.player_y > player_max_y {
-            manager.player_y.sub(player_max_y)
-        } else {
-            i32fx8::default()
+        // Big Mario is a double size sprite, its 32x32 box is centred on the stretched frame
+        let (style, offset_x, offset_y) = match manager.power {
+            PowerState::Small => (ObjDisplayStyle::Affine, 0, 0),
+            PowerState::Big => (ObjDisplayStyle::DoubleSizeAffine, 8, 16),
         };
-
-        let mut to_far = manager.player_x.sub(middle_screen_px);
-        if to_far <= i32fx8::default() {
-            to_far = i32fx8::default();
-        }
-        if to_far != i32fx8::default() || y_diff != i32fx8::default() {
-            ScreenManager::translate(to_far, y_diff);
-        }
-
+        manager.otr.set_style(style);
         manager
             .otr
-            .set_x((manager.player_x.sub(screen.affn_x).to_bits() >> 8) as u16);
+            .set_x(((manager.player_x.sub(screen.affn_x).to_bits() >> 8) - offset_x) as u16);
         manager
             .otr
-            .set_y((manager.player_y.sub(screen.affn_y).to_bits() >> 8) as u16);
-        manager.update_face_dir();
-        OBJ_ATTR_ALL.index(0).write(manager.otr);
-        // manager.otr.write(OBJ_ATTR_ALL.index(0));
+            .set_y(((manager.player_y.sub(screen.affn_y).to_bits() >> 8) - offset_y) as u16);
+        manager.update_transform();
+
+        manager.star_ticks = manager.star_ticks.saturating_sub(1);
+        manager.invulnerable_ticks = manager.invulnerable_ticks.saturating_sub(1);
+        let mut otr = manager.otr;
+        let is_blinking = manager.star_ticks > 0 || manager.invulnerable_ticks > 0;
+        if is_blinking && tick_context.tick_count & 0b10 != 0 {
+            otr.set_style(ObjDisplayStyle::NotDisplayed);
+        }
+        OamManager::write(0, otr);
     }
 }
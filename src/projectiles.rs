@@ -0,0 +1,220 @@
+use gba::prelude::*;
+
+use crate::{
+    assets::{HAMMER_TILE_IDX_START, SPINY_TILE_IDX_START},
+    enemies::{EnemyManager, spiny::Spiny},
+    entity::{EntityBody, Hitbox},
+    ewram_static,
+    fixed_bag::FixedBag,
+    gba_warning,
+    lifts::{LIFT_OAM_START, LIFT_SEGMENTS, MAX_LIFTS},
+    oam::{DYNAMIC_OAM_START, OamManager},
+    player::PlayerManager,
+    screen::{ScreenInfo, ScreenManager},
+    static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+// Thrown things get their own OAM block after the lifts, the last of the fixed blocks
+const PROJECTILE_OAM_START: usize = LIFT_OAM_START + MAX_LIFTS * LIFT_SEGMENTS;
+const MAX_PROJECTILES: usize = 6;
+const _: () = assert!(PROJECTILE_OAM_START + MAX_PROJECTILES <= DYNAMIC_OAM_START);
+
+const HAMMER_SPEED: i32fx8 = i32fx8::from_bits(320);
+const HAMMER_THROW: i32fx8 = i32fx8::from_bits(-1280);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileKind {
+    /// 8x8, arcs through the level ignoring tiles
+    Hammer,
+    /// 16x16, bounces off walls and hatches into a spiny once it lands
+    SpinyEgg,
+}
+
+pub struct Projectile {
+    kind: ProjectileKind,
+    // Top left of the sprite
+    body: EntityBody,
+    anim_tick: u8,
+}
+
+impl Projectile {
+    /// Hammer thrown from (x, y) in world pixels, up and forward in an arc
+    pub fn hammer(x: i32, y: i32, moving_left: bool) -> Self {
+        let mut body = EntityBody::new(i32fx8::wrapping_from(x), i32fx8::wrapping_from(y));
+        body.vel_x = if moving_left {
+            -HAMMER_SPEED
+        } else {
+            HAMMER_SPEED
+        };
+        body.vel_y = HAMMER_THROW;
+        Projectile {
+            kind: ProjectileKind::Hammer,
+            body,
+            anim_tick: 0,
+        }
+    }
+
+    /// Spiny egg dropped from (x, y) in world pixels with the given starting velocity
+    pub fn spiny_egg(x: i32, y: i32, vel_x: i32fx8, vel_y: i32fx8) -> Self {
+        let mut body = EntityBody::new(i32fx8::wrapping_from(x), i32fx8::wrapping_from(y));
+        body.vel_x = vel_x;
+        body.vel_y = vel_y;
+        Projectile {
+            kind: ProjectileKind::SpinyEgg,
+            body,
+            anim_tick: 0,
+        }
+    }
+
+    fn hitbox(&self) -> Hitbox {
+        match self.kind {
+            ProjectileKind::Hammer => Hitbox {
+                x: self.body.px_x() + 1,
+                y: self.body.px_y(),
+                w: 6,
+                h: 8,
+            },
+            ProjectileKind::SpinyEgg => Hitbox {
+                x: self.body.px_x() + 2,
+                y: self.body.px_y() + 4,
+                w: 12,
+                h: 12,
+            },
+        }
+    }
+
+    /// Returns false once the projectile is used up
+    fn tick(&mut self) -> bool {
+        self.anim_tick = self.anim_tick.wrapping_add(1);
+        self.body.apply_gravity();
+
+        match self.kind {
+            ProjectileKind::Hammer => !self.body.step_free(),
+            ProjectileKind::SpinyEgg => {
+                let collision = self.body.step();
+                if collision.fell_out {
+                    return false;
+                }
+                if collision.hit_wall {
+                    self.body.vel_x = -self.body.vel_x;
+                }
+                if !collision.on_ground {
+                    return true;
+                }
+
+                let spiny = Spiny::hatch(self.body, PlayerManager::hitbox());
+                if EnemyManager::spawn(spiny.as_enemy()).is_err() {
+                    gba_warning!("Enemy pool full, spiny egg didn't hatch");
+                }
+                false
+            }
+        }
+    }
+
+    fn post_tick(&self, screen: &ScreenInfo, oam_idx: usize) {
+        let (x, y) = self.body.to_screenspace(screen);
+        let mut otr = ObjAttr::new();
+        otr.set_style(ObjDisplayStyle::Normal);
+        otr.0 = otr
+            .0
+            .with_shape(ObjShape::Square)
+            .with_mode(ObjEffectMode::Normal)
+            .with_bpp8(true);
+        otr.2 = otr.2.with_priority(0).with_palbank(0);
+
+        match self.kind {
+            ProjectileKind::Hammer => {
+                otr.1 = otr
+                    .1
+                    .with_size(0)
+                    // Tumble end over end by cycling through the flips
+                    .with_hflip(self.anim_tick & 0b100 != 0)
+                    .with_vflip(self.anim_tick & 0b1000 != 0);
+                otr.2 = otr.2.with_tile_id(HAMMER_TILE_IDX_START as u16 * 2);
+            }
+            ProjectileKind::SpinyEgg => {
+                otr.1 = otr.1.with_size(1).with_hflip(self.anim_tick & 0b1000 != 0);
+                otr.2 = otr.2.with_tile_id(Spiny::egg_tile_id());
+            }
+        }
+
+        if x < -16 || x >= 240 || y < -16 || y >= 160 {
+            OamManager::hide(oam_idx);
+            return;
+        }
+        otr.set_x(x as u16);
+        otr.set_y(y as u16);
+        OamManager::write(oam_idx, otr);
+    }
+}
+
+pub struct ProjectileManager {
+    projectiles: FixedBag<Projectile, MAX_PROJECTILES>,
+}
+
+impl ProjectileManager {
+    pub const fn new() -> Self {
+        ProjectileManager {
+            projectiles: FixedBag::new(),
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.projectiles.clear();
+        for idx in 0..MAX_PROJECTILES {
+            OamManager::hide(PROJECTILE_OAM_START + idx);
+        }
+    }
+
+    pub fn on_start() {
+        Projectiles.init();
+    }
+
+    pub fn spawn(projectile: Projectile) -> Result<usize, Projectile> {
+        Projectiles.assume_init().projectiles.push(projectile)
+    }
+
+    pub fn tick(_tick: TickContext) {
+        let manager = Projectiles.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        let player = PlayerManager::hitbox();
+
+        for (_idx, slot) in manager.projectiles.iter_mut_opt() {
+            let Some(projectile) = slot else {
+                continue;
+            };
+
+            if !projectile.tick() || projectile.body.is_offscreen(&screen) {
+                *slot = None;
+                continue;
+            }
+
+            if let Some(player) = player
+                && projectile.hitbox().overlaps(&player)
+            {
+                PlayerManager::damage();
+            }
+        }
+    }
+
+    pub fn post_tick() {
+        let manager = Projectiles.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        for idx in 0..MAX_PROJECTILES {
+            let oam_idx = PROJECTILE_OAM_START + idx;
+            match manager.projectiles.get(idx) {
+                Some(projectile) => projectile.post_tick(&screen, oam_idx),
+                None => OamManager::hide(oam_idx),
+            }
+        }
+    }
+}
+
+unsafe impl StaticInitSafe for ProjectileManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Projectiles: ProjectileManager = ProjectileManager::new());
@@ -21,3 +21,8 @@ pub fn next_u8() -> u8 {
 pub fn next_bool() -> bool {
     next_u32() & 0x8000_0000 != 0
 }
+
+/// Adds up to `255 >> shift` random ticks to a timer so repeated actions don't fall into a rhythm
+pub fn jitter(ticks: u16, shift: u32) -> u16 {
+    ticks + (next_u8() >> shift) as u16
+}
@@ -0,0 +1,65 @@
+use crate::{
+    effects::{
+        Effect, EffectPriority, EffectsManager,
+        points::{Points, ScoreAmount},
+    },
+    entity::EntityBody,
+    gba_warning,
+    topbar::TopBarManager,
+};
+
+// Scores without a popup
+const BRICK_SCORE: u32 = 50;
+// Every coin, from a block or the level
+const COIN_AMOUNT: ScoreAmount = ScoreAmount::TwoHundred;
+
+/// Adds the amount to the top bar, or a life for a 1-up
+fn add(amount: ScoreAmount) {
+    match amount {
+        ScoreAmount::OneUp => TopBarManager::add_life(),
+        _ => TopBarManager::add_to_score(amount.value()),
+    }
+}
+
+/// Adds the amount and returns the popup for the caller to play. Popups are at row (8px units)
+/// and col (16px units)
+pub fn score(amount: ScoreAmount, row: usize, col: usize) -> Effect {
+    add(amount);
+    Points::new(row, col, amount).as_effect()
+}
+
+/// Scores the amount and plays its popup straight away
+pub fn award(amount: ScoreAmount, row: usize, col: usize) {
+    let popup = score(amount, row, col);
+    // Losing the popup is fine, the score is already in
+    if EffectsManager::add_effect(popup, 0, EffectPriority::Low).is_err() {
+        gba_warning!("Effect queue full, dropped score popup");
+    }
+}
+
+/// Scores the amount with the popup just above the given body
+pub fn award_above(body: &EntityBody, amount: ScoreAmount) {
+    let row = (body.px_y() >> 3).max(1) as usize - 1;
+    let col = ((body.px_x() + 8) >> 4).max(0) as usize;
+    award(amount, row, col);
+}
+
+/// Popup for a coin knocked out of a block, the coin itself is counted by [`block_coin_award`]
+pub fn block_coin_popup(row: usize, col: usize) -> Effect {
+    Points::new(row, col, COIN_AMOUNT).as_effect()
+}
+
+/// Timeline hook that counts a block coin once its popup has played
+pub fn block_coin_award(_: u32) {
+    coin_collected();
+}
+
+/// A coin picked up straight out of the level
+pub fn coin_collected() {
+    TopBarManager::add_coin();
+    add(COIN_AMOUNT);
+}
+
+pub fn brick_broken() {
+    TopBarManager::add_to_score(BRICK_SCORE);
+}
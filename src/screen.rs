@@ -5,9 +5,147 @@ use gba::{
 
 use crate::{ewram_static, static_init::StaticInitSafe};
 
+const SCREEN_WIDTH: i32 = 240;
+const SCREEN_HEIGHT: i32 = 160;
+// Look-ahead eases in by this fraction (as a shift) of what's left per tick
+const LOOK_AHEAD_EASING: u32 = 4;
+
+/// Box on screen, in pixels, the target can move around in before the camera follows
+#[derive(Clone, Copy)]
+pub struct DeadZone {
+    pub left: i32,
+    pub right: i32,
+    pub top: i32,
+    pub bottom: i32,
+}
+
+#[derive(Clone, Copy)]
+pub struct CameraConfig {
+    pub dead_zone: DeadZone,
+    // Pixels to lead the target by in the direction it faces, 0 turns it off
+    pub look_ahead: i32,
+    // SMB style, once scrolled past the level never comes back
+    pub no_backtracking: bool,
+    // Each tick the camera closes the vertical gap shifted right by this, 0 snaps straight there
+    pub vertical_smoothing: u32,
+}
+
+impl CameraConfig {
+    pub const SMB: CameraConfig = CameraConfig {
+        dead_zone: DeadZone {
+            left: 64,
+            right: 80,
+            top: 45,
+            bottom: 120,
+        },
+        look_ahead: 0,
+        no_backtracking: true,
+        vertical_smoothing: 1,
+    };
+}
+
+pub struct Camera {
+    config: CameraConfig,
+    look_offset: i32fx8,
+    // Furthest the top left of the screen can go, from the level size
+    max_x: i32fx8,
+    max_y: i32fx8,
+}
+
+impl Camera {
+    pub const fn new() -> Self {
+        Camera {
+            config: CameraConfig::SMB,
+            look_offset: i32fx8::from_bits(0),
+            max_x: i32fx8::from_bits(i32::MAX),
+            max_y: i32fx8::from_bits(i32::MAX),
+        }
+    }
+
+    /// Moves (x, y) so the target at (target_x, target_y) in world pixels sits inside the dead zone
+    fn follow(
+        &mut self,
+        x: i32fx8,
+        y: i32fx8,
+        target_x: i32fx8,
+        target_y: i32fx8,
+        facing_right: bool,
+    ) -> (i32fx8, i32fx8) {
+        let config = self.config;
+        let zone = config.dead_zone;
+
+        let look_ahead = if facing_right {
+            config.look_ahead
+        } else {
+            -config.look_ahead
+        };
+        let look_gap = i32fx8::wrapping_from(look_ahead).sub(self.look_offset);
+        self.look_offset = self
+            .look_offset
+            .add(i32fx8::from_bits(look_gap.to_bits() >> LOOK_AHEAD_EASING));
+        let target_x = target_x.add(self.look_offset);
+
+        let right = x.add(i32fx8::wrapping_from(zone.right));
+        let left = x.add(i32fx8::wrapping_from(zone.left));
+        let mut new_x = x;
+        if target_x > right {
+            new_x = target_x.sub(i32fx8::wrapping_from(zone.right));
+        } else if target_x < left && !config.no_backtracking {
+            new_x = target_x.sub(i32fx8::wrapping_from(zone.left));
+        }
+
+        let top = y.add(i32fx8::wrapping_from(zone.top));
+        let bottom = y.add(i32fx8::wrapping_from(zone.bottom));
+        let gap_y = if target_y < top {
+            target_y.sub(top)
+        } else if target_y > bottom {
+            target_y.sub(bottom)
+        } else {
+            i32fx8::default()
+        };
+        let mut step_y = gap_y.to_bits() >> config.vertical_smoothing;
+        // Always make some progress so the camera doesn't stall a fraction short
+        if step_y == 0 {
+            step_y = gap_y.to_bits().signum();
+        }
+        let new_y = y.add(i32fx8::from_bits(step_y));
+
+        let zero = i32fx8::default();
+        (
+            new_x.max(zero).min(self.max_x.max(zero)),
+            new_y.max(zero).min(self.max_y.max(zero)),
+        )
+    }
+}
+
+/// Offset that flips direction every frame and dies down to nothing over its length
+#[derive(Clone, Copy)]
+struct Impulse {
+    x: i32,
+    y: i32,
+    ticks: u16,
+    total_ticks: u16,
+}
+
+impl Impulse {
+    fn offset(&self) -> (i32, i32) {
+        let remaining = self.ticks as i32;
+        let total = self.total_ticks.max(1) as i32;
+        let sign = if self.ticks & 1 == 0 { 1 } else { -1 };
+        (
+            sign * self.x * remaining / total,
+            sign * self.y * remaining / total,
+        )
+    }
+}
+
 pub struct ScreenManager {
     pub affn_x: i32fx8,
     pub affn_y: i32fx8,
+    camera: Camera,
+    impulse: Option<Impulse>,
+    // Drawing offset for this frame, kept out of affn_x/affn_y so the level logic never sees it
+    shake_offset: (i32, i32),
 }
 
 unsafe impl StaticInitSafe for ScreenManager {
@@ -36,6 +174,9 @@ impl ScreenManager {
         ScreenManager {
             affn_x: i32fx8::wrapping_from(0),
             affn_y: i32fx8::wrapping_from(0),
+            camera: Camera::new(),
+            impulse: None,
+            shake_offset: (0, 0),
         }
     }
 
@@ -43,7 +184,10 @@ impl ScreenManager {
         Screen.init();
     }
 
-    fn reset_internal(&mut self) {}
+    fn reset_internal(&mut self) {
+        self.impulse = None;
+        self.shake_offset = (0, 0);
+    }
 
     pub fn get_screen_info() -> ScreenInfo {
         let screen = Screen.assume_init();
@@ -59,19 +203,77 @@ impl ScreenManager {
         screen.affn_x = screen.affn_x.add(amount);
     }
 
-    pub fn translate(x: i32fx8, y: i32fx8) {
+    pub fn set_camera_config(config: CameraConfig) {
+        Screen.assume_init().camera.config = config;
+    }
+
+    /// Stops the camera at the edges of a level this size, in pixels
+    pub fn set_level_bounds(width: i32, height: i32) {
+        let camera = &mut Screen.assume_init().camera;
+        camera.max_x = i32fx8::wrapping_from(width - SCREEN_WIDTH);
+        camera.max_y = i32fx8::wrapping_from(height - SCREEN_HEIGHT);
+    }
+
+    /// Scrolls to keep the target, in world pixels, inside the camera's dead zone
+    pub fn follow(target_x: i32fx8, target_y: i32fx8, facing_right: bool) {
         let screen = Screen.assume_init();
-        screen.affn_x = screen.affn_x.add(x);
-        screen.affn_y = screen.affn_y.add(y);
-        if screen.affn_y > i32fx8::wrapping_from(10 * 10) {
-            screen.affn_y = i32fx8::wrapping_from(10 * 10);
+        let (x, y) = screen.camera.follow(
+            screen.affn_x,
+            screen.affn_y,
+            target_x,
+            target_y,
+            facing_right,
+        );
+        screen.affn_x = x;
+        screen.affn_y = y;
+    }
+
+    /// Jolts the view by up to (x, y) pixels for the given ticks, weaker impulses don't cut off a
+    /// stronger one still playing
+    pub fn impulse(x: i32, y: i32, ticks: u16) {
+        let manager = Screen.assume_init();
+        let strength = |(x, y): (i32, i32)| x.abs() + y.abs();
+        if let Some(current) = manager.impulse
+            && strength(current.offset()) > strength((x, y))
+        {
+            return;
         }
+        manager.impulse = Some(Impulse {
+            x,
+            y,
+            ticks,
+            total_ticks: ticks,
+        });
+    }
+
+    pub fn shake(amplitude: i32, ticks: u16) {
+        Self::impulse(amplitude, amplitude, ticks);
+    }
+
+    /// How far the background is drawn from the camera this frame, sprites move the other way
+    pub fn shake_offset() -> (i32, i32) {
+        Screen.assume_init().shake_offset
     }
 
     pub fn post_tick() {
         let manager = Screen.assume_init();
-        // manager.affn_y = manager.affn_y.add(i32fx8::from_bits(8));
-        BG2X.write(manager.affn_x);
-        BG2Y.write(manager.affn_y);
+        manager.shake_offset = match &mut manager.impulse {
+            Some(impulse) if impulse.ticks > 0 => {
+                impulse.ticks -= 1;
+                impulse.offset()
+            }
+            _ => {
+                manager.impulse = None;
+                (0, 0)
+            }
+        };
+    }
+
+    /// Applies the scroll, runs at VBlank alongside the OAM copy so sprites and background stay in step
+    pub fn on_vblank() {
+        let manager = Screen.assume_init();
+        let (x, y) = manager.shake_offset;
+        BG2X.write(manager.affn_x.add(i32fx8::wrapping_from(x)));
+        BG2Y.write(manager.affn_y.add(i32fx8::wrapping_from(y)));
     }
 }
@@ -0,0 +1,176 @@
+use gba::prelude::*;
+
+use crate::{
+    enemies::{Enemy, EnemyManager, bullet_bill::BulletBill, cheep_cheep::CheepCheep},
+    entity::Hitbox,
+    ewram_static,
+    fixed_bag::FixedBag,
+    gba_warning,
+    levels::shared::SpawnerKind,
+    player::PlayerManager,
+    rng,
+    screen::{ScreenInfo, ScreenManager},
+    static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+const MAX_SPAWNERS: usize = 4;
+
+// Blasters hold their fire while the player is standing this close, so they can be jumped on
+const BLASTER_MIN_DISTANCE: i32 = 40;
+const BLASTER_TICKS: u16 = 120;
+const MAX_BULLET_BILLS: usize = 3;
+
+const CHEEP_CHEEP_TICKS: u16 = 48;
+const MAX_CHEEP_CHEEPS: usize = 3;
+const CHEEP_CHEEP_LEAP: i32 = -1152;
+const CHEEP_CHEEP_SPEED: i32 = 128;
+
+impl SpawnerKind {
+    /// Ticks between spawns, before the jitter
+    fn ticks(self) -> u16 {
+        match self {
+            SpawnerKind::BulletBlaster => BLASTER_TICKS,
+            SpawnerKind::CheepCheeps { .. } => CHEEP_CHEEP_TICKS,
+        }
+    }
+}
+
+pub struct Spawner {
+    kind: SpawnerKind,
+    row: usize,
+    col: usize,
+    ticks: u16,
+}
+
+impl Spawner {
+    /// Spawner for a level item on row (8px units) and col (16px units)
+    fn new(kind: SpawnerKind, row: usize, col: usize) -> Self {
+        Spawner {
+            kind,
+            row,
+            col,
+            ticks: rng::jitter(kind.ticks(), 2),
+        }
+    }
+
+    /// Counts down while the camera conditions hold and emits an enemy when the timer runs out.
+    /// Returns false once the camera has moved past the spawner for good
+    fn tick(&mut self, screen: &ScreenInfo, player: Option<Hitbox>) -> bool {
+        let screen_x = screen.affn_x.to_bits() >> 8;
+        let x = (self.col << 4) as i32;
+
+        match self.kind {
+            SpawnerKind::BulletBlaster => {
+                if x + 16 < screen_x {
+                    return false;
+                }
+                let Some(player) = player else {
+                    return true;
+                };
+                let is_onscreen = x < screen_x + 240;
+                let is_player_close = (player.x - x).abs() < BLASTER_MIN_DISTANCE;
+                if !is_onscreen || is_player_close || !self.count_down() {
+                    return true;
+                }
+
+                let live = EnemyManager::count(|enemy| matches!(enemy, Enemy::BulletBill(_)));
+                if live < MAX_BULLET_BILLS {
+                    let moving_left = player.x < x;
+                    spawn(BulletBill::new(self.row, self.col, moving_left).as_enemy());
+                }
+            }
+            SpawnerKind::CheepCheeps { len } => {
+                let end_x = x + ((len as i32) << 4);
+                if end_x < screen_x {
+                    return false;
+                }
+                let is_over_zone = x < screen_x + 240;
+                if !is_over_zone || !self.count_down() {
+                    return true;
+                }
+
+                let live = EnemyManager::count(|enemy| matches!(enemy, Enemy::CheepCheep(_)));
+                if live < MAX_CHEEP_CHEEPS {
+                    spawn(Self::cheep_cheep(screen).as_enemy());
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns true when the timer runs out, restarting it
+    fn count_down(&mut self) -> bool {
+        self.ticks = self.ticks.saturating_sub(1);
+        if self.ticks > 0 {
+            return false;
+        }
+        self.ticks = rng::jitter(self.kind.ticks(), 2);
+        true
+    }
+
+    /// Cheep-cheep leaping up from a random spot just below the bottom of the screen
+    fn cheep_cheep(screen: &ScreenInfo) -> CheepCheep {
+        let screen_x = screen.affn_x.to_bits() >> 8;
+        let screen_y = screen.affn_y.to_bits() >> 8;
+        let x = screen_x + 16 + ((rng::next_u8() as i32 * 208) >> 8);
+        let y = screen_y + 160;
+
+        let speed = CHEEP_CHEEP_SPEED + (rng::next_u8() >> 1) as i32;
+        let vel_x = if rng::next_bool() { speed } else { -speed };
+        let vel_y = CHEEP_CHEEP_LEAP - (rng::next_u8() as i32);
+        CheepCheep::leap(x, y, i32fx8::from_bits(vel_x), i32fx8::from_bits(vel_y))
+    }
+}
+
+fn spawn(enemy: Enemy) {
+    if EnemyManager::spawn(enemy).is_err() {
+        gba_warning!("Enemy pool full, spawner skipped a turn");
+    }
+}
+
+pub struct SpawnerManager {
+    spawners: FixedBag<Spawner, MAX_SPAWNERS>,
+}
+
+impl SpawnerManager {
+    pub const fn new() -> Self {
+        SpawnerManager {
+            spawners: FixedBag::new(),
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.spawners.clear();
+    }
+
+    pub fn on_start() {
+        Spawners.init();
+    }
+
+    /// Starts a spawner for a level item on row (8px units) and col (16px units)
+    pub fn add(kind: SpawnerKind, row: usize, col: usize) -> Result<usize, SpawnerKind> {
+        Spawners
+            .assume_init()
+            .spawners
+            .push(Spawner::new(kind, row, col))
+            .map_err(|spawner| spawner.kind)
+    }
+
+    pub fn tick(_tick: TickContext) {
+        let manager = Spawners.assume_init();
+        let screen = ScreenManager::get_screen_info();
+        let player = PlayerManager::hitbox();
+        manager
+            .spawners
+            .iter_filter(|spawner| spawner.tick(&screen, player));
+    }
+}
+
+unsafe impl StaticInitSafe for SpawnerManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Spawners: SpawnerManager = SpawnerManager::new());
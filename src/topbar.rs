@@ -18,6 +18,8 @@ pub struct TopBarManager {
     pub score: u32,
     pub time: u16,
     pub time_tick: u8,
+    pub coins: u16,
+    pub lives: u8,
     new_score: Option<u32>,
     palette_handle: Option<TextPalette<4>>,
 }
@@ -34,6 +36,9 @@ ewram_static!(TIME_STR: FixedString<3> = FixedString::new());
 ewram_static!(TopBar: TopBarManager = TopBarManager::new());
 
 const TIME_LOC: (usize, usize) = (26, 0);
+const COIN_LOC: (usize, usize) = (10, 0);
+const COINS_PER_LIFE: u16 = 100;
+const STARTING_LIVES: u8 = 3;
 
 impl TopBarManager {
     pub const fn new() -> Self {
@@ -41,6 +46,8 @@ impl TopBarManager {
             score: 0,
             time: 400,
             time_tick: 0,
+            coins: 0,
+            lives: STARTING_LIVES,
             new_score: Some(0),
             palette_handle: None,
         }
@@ -57,14 +64,8 @@ impl TopBarManager {
         ));
         self.write_score();
         self.write_time();
+        self.write_coins();
 
-        self.palette_handle.as_mut().unwrap().write_text(
-            0,
-            TEXT_SCREENBLOCK_START,
-            "Cx00",
-            (10, 0),
-            false,
-        );
         self.palette_handle.as_mut().unwrap().write_text(
             1,
             TEXT_SCREENBLOCK_START,
@@ -101,8 +102,24 @@ impl TopBarManager {
         );
     }
 
+    fn write_coins(&mut self) {
+        let digits = to_dec_u16::<2>(self.coins);
+        let mut buf = [b'C', b'x', b'0', b'0'];
+        buf[2..].copy_from_slice(digits.as_bytes());
+        let s = unsafe { core::str::from_utf8_unchecked(&buf) };
+        self.palette_handle.as_mut().unwrap().write_text(
+            0,
+            TEXT_SCREENBLOCK_START,
+            s,
+            COIN_LOC,
+            false,
+        );
+    }
+
     pub fn reset(score: u32) {
-        TopBar.get_or_init().reset_internal(score);
+        let manager = TopBar.get_or_init();
+        manager.coins = 0;
+        manager.reset_internal(score);
     }
 
     pub fn reset_w_score() {
@@ -117,11 +134,25 @@ impl TopBarManager {
 
     pub fn add_to_score(score: u32) {
         let manager = TopBar.get_or_init();
-        let mut cur_score = manager.score + score;
-        if let Some(ok) = manager.new_score {
-            cur_score += ok;
+        // A score already waiting to be written includes the current one
+        let cur_score = manager.new_score.unwrap_or(manager.score);
+        manager.new_score = Some(cur_score + score);
+    }
+
+    /// Counts a collected coin, every 100 coins rolls the counter over into an extra life
+    pub fn add_coin() {
+        let manager = TopBar.get_or_init();
+        manager.coins += 1;
+        if manager.coins >= COINS_PER_LIFE {
+            manager.coins = 0;
+            manager.lives = manager.lives.saturating_add(1);
         }
-        manager.new_score = Some(cur_score);
+        manager.write_coins();
+    }
+
+    pub fn add_life() {
+        let manager = TopBar.get_or_init();
+        manager.lives = manager.lives.saturating_add(1);
     }
 
     pub fn tick(_tick_context: TickContext) {
@@ -0,0 +1,275 @@
+use gba::prelude::*;
+
+use crate::{
+    ewram_static, math::isqrt_u32, screen::ScreenManager, static_init::StaticInitSafe,
+    tick::TickContext,
+};
+
+// Copies one halfword of window edges into the fixed WIN0 register at the start of every HBlank,
+// picking up from the next line each time
+const IRIS_DMA: DmaControl = DmaControl::new()
+    .with_dest_addr_control(DestAddrControl::Fixed)
+    .with_src_addr_control(SrcAddrControl::Increment)
+    .with_start_time(DmaStartTime::HBlank)
+    .with_repeat(true)
+    .with_transfer_32bit(false)
+    .with_enabled(true);
+
+// Fades darken or brighten every layer together
+const FADE_TARGETS: BlendControl = BlendControl::new()
+    .with_target1_bg1(true)
+    .with_target1_bg2(true)
+    .with_target1_obj(true)
+    .with_target1_backdrop(true);
+
+// Blend coefficients go from 0 to 16 in 16ths
+const FULL: u8 = 16;
+// Progress through a transition is kept in 256ths so the iris can move a pixel at a time
+const PROGRESS_END: u32 = 256;
+
+const SCREEN_WIDTH: i32 = 240;
+const SCREEN_HEIGHT: usize = 160;
+// Far enough to clear every corner wherever the centre is on screen
+const IRIS_MAX_RADIUS: i32 = 288;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    FadeToBlack,
+    FadeFromBlack,
+    FadeToWhite,
+    FadeFromWhite,
+    // Fades the text layer out over the level, for going from the title or intro to gameplay
+    CrossFade,
+    // Circle around a point in world pixels shrinking to black, the text layer stays on top
+    IrisClose { x: i32, y: i32 },
+    IrisOpen { x: i32, y: i32 },
+}
+
+impl Transition {
+    fn control(self) -> BlendControl {
+        match self {
+            Transition::FadeToBlack | Transition::FadeFromBlack => {
+                FADE_TARGETS.with_mode(ColorEffectMode::Darken)
+            }
+            Transition::FadeToWhite | Transition::FadeFromWhite => {
+                FADE_TARGETS.with_mode(ColorEffectMode::Brighten)
+            }
+            Transition::CrossFade => BlendControl::new()
+                .with_mode(ColorEffectMode::AlphaBlend)
+                .with_target1_bg1(true)
+                .with_target2_bg2(true)
+                .with_target2_obj(true)
+                .with_target2_backdrop(true),
+            // Outside the window only the backdrop and BG1 show, and the backdrop blends to black
+            Transition::IrisClose { .. } | Transition::IrisOpen { .. } => BlendControl::new()
+                .with_mode(ColorEffectMode::Darken)
+                .with_target1_backdrop(true),
+        }
+    }
+
+    fn is_iris(self) -> bool {
+        matches!(
+            self,
+            Transition::IrisClose { .. } | Transition::IrisOpen { .. }
+        )
+    }
+
+    /// Writes the blend amount for `progress` 256ths of the way through
+    fn apply(self, progress: u32, lines: &mut [u16; SCREEN_HEIGHT + 1]) {
+        let amount = (progress * FULL as u32 / PROGRESS_END) as u8;
+        match self {
+            Transition::FadeToBlack | Transition::FadeToWhite => BLDY.write(amount),
+            Transition::FadeFromBlack | Transition::FadeFromWhite => BLDY.write(FULL - amount),
+            Transition::CrossFade => {
+                BLDALPHA_A.write(FULL - amount);
+                BLDALPHA_B.write(amount);
+            }
+            Transition::IrisClose { x, y } => {
+                BLDY.write(FULL);
+                let radius = IRIS_MAX_RADIUS * (PROGRESS_END - progress) as i32 / 256;
+                iris_lines(x, y, radius, lines);
+            }
+            Transition::IrisOpen { x, y } => {
+                BLDY.write(FULL);
+                let radius = IRIS_MAX_RADIUS * progress as i32 / 256;
+                iris_lines(x, y, radius, lines);
+            }
+        }
+    }
+
+    /// Fades to black and white and the cross fade hold their last frame until the next
+    /// transition, the rest let go
+    fn holds_at_end(self) -> bool {
+        matches!(
+            self,
+            Transition::FadeToBlack
+                | Transition::FadeToWhite
+                | Transition::CrossFade
+                | Transition::IrisClose { .. }
+        )
+    }
+}
+
+/// Fills in the left and right edge of the window on each scanline for a circle around the world
+/// point (x, y)
+fn iris_lines(x: i32, y: i32, radius: i32, lines: &mut [u16; SCREEN_HEIGHT + 1]) {
+    let screen = ScreenManager::get_screen_info();
+    let center_x = x - (screen.affn_x.to_bits() >> 8);
+    let center_y = y - (screen.affn_y.to_bits() >> 8);
+    let radius_sq = (radius * radius) as u32;
+
+    for (line, bounds) in lines.iter_mut().take(SCREEN_HEIGHT).enumerate() {
+        let dy = (line as i32 - center_y).unsigned_abs();
+        if dy * dy >= radius_sq {
+            *bounds = 0;
+            continue;
+        }
+        let half = isqrt_u32(radius_sq - dy * dy) as i32;
+        let left = (center_x - half).clamp(0, SCREEN_WIDTH) as u16;
+        let right = (center_x + half).clamp(0, SCREEN_WIDTH) as u16;
+        *bounds = (left << 8) | right;
+    }
+}
+
+struct ActiveTransition {
+    transition: Transition,
+    tick: u16,
+    frames: u16,
+    on_done: Option<fn()>,
+}
+
+/// Screen-wide fades through the blend registers and iris wipes through window 0, timed in frames
+pub struct TransitionManager {
+    active: Option<ActiveTransition>,
+    // The finished transition still showing, if it holds its last frame
+    holding: Option<Transition>,
+    // Window 0 edges per scanline, built during the frame and handed to the HBlank DMA at VBlank
+    iris_lines: [[u16; SCREEN_HEIGHT + 1]; 2],
+    front_lines: usize,
+    is_iris_shown: bool,
+}
+
+impl TransitionManager {
+    pub const fn new() -> Self {
+        TransitionManager {
+            active: None,
+            holding: None,
+            iris_lines: [[0; SCREEN_HEIGHT + 1]; 2],
+            front_lines: 0,
+            is_iris_shown: false,
+        }
+    }
+
+    fn reset_internal(&mut self) {
+        self.active = None;
+        self.holding = None;
+        self.is_iris_shown = false;
+        BLDCNT.write(BlendControl::new());
+        DISPCNT.write(DISPCNT.read().with_show_win0(false));
+    }
+
+    pub fn on_start() {
+        Transitions.init();
+    }
+
+    /// Starts the transition, replacing any still running. The hook runs on the tick it finishes
+    pub fn start(transition: Transition, frames: u16, on_done: Option<fn()>) {
+        let manager = Transitions.assume_init();
+        manager.holding = None;
+        manager.active = Some(ActiveTransition {
+            transition,
+            tick: 0,
+            frames: frames.max(1),
+            on_done,
+        });
+        BLDCNT.write(transition.control());
+    }
+
+    /// For game state code waiting on a transition, true once nothing is running
+    pub fn is_done() -> bool {
+        Transitions.assume_init().active.is_none()
+    }
+
+    pub fn tick(_tick: TickContext) {
+        let manager = Transitions.assume_init();
+        let Some(active) = &mut manager.active else {
+            return;
+        };
+        active.tick += 1;
+        if active.tick < active.frames {
+            return;
+        }
+
+        let transition = active.transition;
+        let on_done = active.on_done;
+        manager.active = None;
+        manager.holding = transition.holds_at_end().then_some(transition);
+        if let Some(on_done) = on_done {
+            on_done();
+        }
+    }
+
+    /// Works out this frame's blend amount or iris
+    pub fn post_tick() {
+        let manager = Transitions.assume_init();
+        let (transition, progress) = if let Some(active) = &manager.active {
+            let progress = active.tick as u32 * PROGRESS_END / active.frames as u32;
+            (active.transition, progress)
+        } else if let Some(holding) = manager.holding {
+            (holding, PROGRESS_END)
+        } else {
+            BLDCNT.write(BlendControl::new());
+            manager.is_iris_shown = false;
+            return;
+        };
+
+        let back_lines = &mut manager.iris_lines[manager.front_lines ^ 1];
+        transition.apply(progress, back_lines);
+        manager.is_iris_shown = transition.is_iris();
+    }
+
+    /// Points the HBlank DMA at the window edges for the frame about to be drawn
+    pub fn on_vblank() {
+        let manager = Transitions.assume_init();
+        unsafe { DMA0_CONTROL.write(DmaControl::new()) };
+        if !manager.is_iris_shown {
+            DISPCNT.write(DISPCNT.read().with_show_win0(false));
+            return;
+        }
+
+        manager.front_lines ^= 1;
+        let lines = &manager.iris_lines[manager.front_lines];
+        WIN_IN.write(
+            WindowInside::new()
+                .with_win0_bg1(true)
+                .with_win0_bg2(true)
+                .with_win0_obj(true),
+        );
+        WIN_OUT.write(
+            WindowOutside::new()
+                .with_outside_bg1(true)
+                .with_outside_effect(true),
+        );
+        WIN0_TOP.write(0);
+        WIN0_BOTTOM.write(SCREEN_HEIGHT as u8);
+        // Line 0 is set here, the DMA fills in each following line during the HBlank before it
+        let [right, left] = lines[0].to_le_bytes();
+        WIN0_LEFT.write(left);
+        WIN0_RIGHT.write(right);
+        unsafe {
+            DMA0_SRC.write(lines[1..].as_ptr().cast());
+            DMA0_DEST.write(WIN0_RIGHT.as_usize() as *mut _);
+            DMA0_COUNT.write(1);
+            DMA0_CONTROL.write(IRIS_DMA);
+        }
+        DISPCNT.write(DISPCNT.read().with_show_win0(true));
+    }
+}
+
+unsafe impl StaticInitSafe for TransitionManager {
+    fn init(&mut self) {
+        self.reset_internal();
+    }
+}
+
+ewram_static!(Transitions: TransitionManager = TransitionManager::new());